@@ -0,0 +1,78 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use orx_concurrent_queue::ConcurrentQueue;
+use std::hint::black_box;
+
+const LEN: usize = 1 << 16;
+const NUM_THREADS: usize = 4;
+
+fn queue() -> ConcurrentQueue<u64> {
+    (0..LEN as u64).collect()
+}
+
+fn pop(queue: &ConcurrentQueue<u64>) -> u64 {
+    let mut sum = 0;
+    while let Some(x) = queue.pop() {
+        sum += x;
+    }
+    sum
+}
+
+fn pull(queue: &ConcurrentQueue<u64>, chunk_size: usize) -> u64 {
+    let mut sum = 0;
+    while let Some(chunk) = queue.pull(chunk_size) {
+        sum += chunk.sum::<u64>();
+    }
+    sum
+}
+
+fn concurrently(
+    queue: &ConcurrentQueue<u64>,
+    consume: impl Fn(&ConcurrentQueue<u64>) -> u64 + Sync,
+) -> u64 {
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..NUM_THREADS)
+            .map(|_| s.spawn(|| consume(queue)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|x| x.join().expect("consumers do not panic"))
+            .sum()
+    })
+}
+
+fn bench_pop_pull(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pop_pull");
+
+    group.bench_function(BenchmarkId::new("pop", 1), |b| {
+        b.iter_batched(
+            queue,
+            |q| black_box(pop(&q)),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.bench_function(BenchmarkId::new("pop", NUM_THREADS), |b| {
+        b.iter_batched(
+            queue,
+            |q| black_box(concurrently(&q, pop)),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+
+    for chunk_size in [4, 64] {
+        group.bench_function(
+            BenchmarkId::new(format!("pull-{chunk_size}"), NUM_THREADS),
+            |b| {
+                b.iter_batched(
+                    queue,
+                    |q| black_box(concurrently(&q, |q| pull(q, chunk_size))),
+                    criterion::BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pop_pull);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+/// Policy determining when the underlying storage of the queue allocates new capacity.
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::AllocationPolicy;
+///
+/// let ahead = AllocationPolicy::Ahead { usage_percent: 75 };
+/// assert_eq!(ahead.threshold(1000), Some(750));
+///
+/// assert_eq!(AllocationPolicy::OnDemand.threshold(1000), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AllocationPolicy {
+    /// New capacity is allocated when a producer reaches the end of the allocated capacity.
+    ///
+    /// While this producer allocates, all other producers whose positions are beyond the capacity wait for the allocation.
+    #[default]
+    OnDemand,
+    /// The next fragment is allocated as soon as `usage_percent` of the allocated capacity is reserved by the producers.
+    ///
+    /// Allocation is performed by the producer crossing the threshold after it writes its own elements, while
+    /// the other producers keep writing to the already allocated positions.
+    /// Therefore, producers rarely need to wait for an allocation on the hot path.
+    ///
+    /// At most one fragment is allocated ahead of demand; the next one is allocated only after the producers
+    /// reserve positions within it. For instance, `usage_percent` of 0 always keeps one spare fragment.
+    ///
+    /// Percentages greater than 100 are treated as 100, which is equivalent to [`AllocationPolicy::OnDemand`].
+    ///
+    /// [`AllocationPolicy::OnDemand`]: crate::AllocationPolicy::OnDemand
+    Ahead {
+        /// Percentage of the allocated capacity which triggers the allocation of the next fragment.
+        usage_percent: u8,
+    },
+}
+
+impl AllocationPolicy {
+    /// Returns the number of reserved positions beyond which the next fragment is allocated ahead of demand
+    /// when the allocated capacity is `capacity`; returns None if the storage grows only on demand.
+    pub fn threshold(&self, capacity: usize) -> Option<usize> {
+        match *self {
+            Self::OnDemand => None,
+            Self::Ahead { usage_percent } => {
+                let percent = (usage_percent as usize).min(100);
+                Some(capacity / 100 * percent + capacity % 100 * percent / 100)
+            }
+        }
+    }
+}
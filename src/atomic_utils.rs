@@ -1,4 +1,7 @@
-use core::sync::atomic::{AtomicUsize, Ordering};
+use core::{
+    ops::Range,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
 
 #[inline(always)]
 pub fn comp_exch_weak(atom: &AtomicUsize, current: usize, new: usize) -> Result<usize, usize> {
@@ -9,3 +12,162 @@ pub fn comp_exch_weak(atom: &AtomicUsize, current: usize, new: usize) -> Result<
 pub fn comp_exch(atom: &AtomicUsize, current: usize, new: usize) -> Result<usize, usize> {
     atom.compare_exchange(current, new, Ordering::Release, Ordering::Relaxed)
 }
+
+/// Compare exchange claiming positions of the queue, which is sequentially consistent with the registration
+/// of the readers; see [`Readers`].
+#[inline(always)]
+pub fn comp_exch_seq_cst(atom: &AtomicUsize, current: usize, new: usize) -> Result<usize, usize> {
+    atom.compare_exchange(current, new, Ordering::SeqCst, Ordering::Relaxed)
+}
+
+/// Maximum number of readers concurrently reading the same queue with narrowed ranges; further readers do not
+/// wait for a free slot, but they read all positions instead.
+const NUM_READER_SLOTS: usize = 8;
+
+/// Ranges of positions which are being read by the readers of a queue, such that a thread claiming positions
+/// waits only for the readers of the claimed positions.
+///
+/// A reader first takes a slot covering all positions, registers and then loads the claimed counter before narrowing
+/// its range, while a thread claiming positions first updates the claimed counter and then checks the registered
+/// readers, all sequentially consistent.
+/// Therefore, either the reader observes the claim and excludes the claimed positions, or the claiming thread
+/// observes the range of the reader.
+///
+/// Registration never waits. When all slots are taken, the reader is counted among the overflowing readers,
+/// which cover all positions; hence, claiming threads wait for them regardless of the claimed positions.
+pub struct Readers {
+    num_readers: AtomicUsize,
+    num_overflowing: AtomicUsize,
+    slots: [ReaderSlot; NUM_READER_SLOTS],
+}
+
+/// Range of positions `begin..end` read by a reader; the slot is free while `begin` is `usize::MAX`.
+struct ReaderSlot {
+    begin: AtomicUsize,
+    end: AtomicUsize,
+}
+
+impl ReaderSlot {
+    const fn free() -> Self {
+        Self {
+            begin: AtomicUsize::new(usize::MAX),
+            end: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline(always)]
+    fn overlaps(&self, range: &Range<usize>) -> bool {
+        let begin = self.begin.load(Ordering::SeqCst);
+        let end = self.end.load(Ordering::SeqCst);
+        begin < range.end && range.start < end
+    }
+}
+
+impl Default for Readers {
+    fn default() -> Self {
+        Self {
+            num_readers: AtomicUsize::new(0),
+            num_overflowing: AtomicUsize::new(0),
+            slots: [const { ReaderSlot::free() }; NUM_READER_SLOTS],
+        }
+    }
+}
+
+impl Readers {
+    /// Registers a reader of all positions, which narrows its range by [`ReaderGuard::set_range`]
+    /// and unregisters when the guard is dropped.
+    ///
+    /// Never waits; a reader registering while all slots are taken keeps reading all positions.
+    pub fn register(&self) -> ReaderGuard<'_> {
+        let slot = self.slots.iter().find(|slot| {
+            slot.begin
+                .compare_exchange(usize::MAX, 0, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+        });
+        match slot {
+            Some(slot) => slot.end.store(usize::MAX, Ordering::SeqCst),
+            None => _ = self.num_overflowing.fetch_add(1, Ordering::SeqCst),
+        }
+        self.num_readers.fetch_add(1, Ordering::SeqCst);
+        ReaderGuard {
+            readers: self,
+            slot,
+        }
+    }
+
+    /// Spins until none of the registered readers reads any of the positions in `range`.
+    ///
+    /// Costs a single atomic load when there exist no readers.
+    #[inline(always)]
+    pub fn wait_for_readers_of(&self, range: Range<usize>) {
+        while self.num_readers.load(Ordering::SeqCst) > 0
+            && (self.num_overflowing.load(Ordering::SeqCst) > 0
+                || self.slots.iter().any(|x| x.overlaps(&range)))
+        {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Guard of a registered reader, which unregisters the reader when dropped.
+pub struct ReaderGuard<'a> {
+    readers: &'a Readers,
+    slot: Option<&'a ReaderSlot>,
+}
+
+impl ReaderGuard<'_> {
+    /// Narrows the range of positions read by the reader down to `range`, which must be within its current range.
+    ///
+    /// Does nothing for an overflowing reader, which keeps reading all positions.
+    #[inline(always)]
+    pub fn set_range(&self, range: Range<usize>) {
+        if let Some(slot) = self.slot {
+            // the end is narrowed first so that the range observed by other threads is never narrower than the actual one
+            slot.end.store(range.end, Ordering::SeqCst);
+            slot.begin.store(range.start, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Drop for ReaderGuard<'_> {
+    fn drop(&mut self) {
+        match self.slot {
+            Some(slot) => {
+                slot.end.store(0, Ordering::Release);
+                slot.begin.store(usize::MAX, Ordering::Release);
+            }
+            None => _ = self.readers.num_overflowing.fetch_sub(1, Ordering::Release),
+        }
+        self.readers.num_readers.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Guard of a lock acquired on an atomic flag, which releases the lock when dropped.
+pub struct FlagGuard<'a>(&'a AtomicBool);
+
+impl<'a> FlagGuard<'a> {
+    /// Acquires the lock by spinning until the `flag` is set by this thread.
+    #[inline(always)]
+    pub fn lock(flag: &'a AtomicBool) -> Self {
+        loop {
+            if let Some(guard) = Self::try_lock(flag) {
+                return guard;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Acquires the lock if the `flag` is not set; returns None otherwise.
+    #[inline(always)]
+    pub fn try_lock(flag: &'a AtomicBool) -> Option<Self> {
+        flag.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Self(flag))
+    }
+}
+
+impl Drop for FlagGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,84 @@
+use alloc::boxed::Box;
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ops::Range,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// A fixed capacity fragment of the broadcast queue holding the elements at positions `begin_idx..end_idx`.
+///
+/// Fragments are linked to each other in the order of their positions, and they are freed from the front
+/// once all subscribers have passed them.
+pub(super) struct Fragment<T> {
+    begin_idx: usize,
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    next: AtomicPtr<Fragment<T>>,
+}
+
+impl<T> Fragment<T> {
+    /// Allocates a fragment with the given `capacity` starting at position `begin_idx`, and returns its pointer.
+    pub fn allocate(begin_idx: usize, capacity: usize) -> *mut Self {
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        let fragment = Self {
+            begin_idx,
+            slots,
+            next: AtomicPtr::new(ptr::null_mut()),
+        };
+        Box::into_raw(Box::new(fragment))
+    }
+
+    /// Frees the memory of the fragment without dropping any of its elements.
+    ///
+    /// # Safety
+    ///
+    /// The `fragment` must be allocated by [`Fragment::allocate`], it must not be freed before,
+    /// and it must not be accessed afterwards.
+    pub unsafe fn free(fragment: *mut Self) {
+        drop(unsafe { Box::from_raw(fragment) });
+    }
+
+    #[inline(always)]
+    pub fn begin_idx(&self) -> usize {
+        self.begin_idx
+    }
+
+    #[inline(always)]
+    pub fn end_idx(&self) -> usize {
+        self.begin_idx + self.slots.len()
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, idx: usize) -> bool {
+        self.begin_idx <= idx && idx < self.end_idx()
+    }
+
+    /// Returns the next fragment; null if the next fragment is not allocated yet.
+    #[inline(always)]
+    pub fn next(&self) -> *mut Self {
+        self.next.load(Ordering::Acquire)
+    }
+
+    #[inline(always)]
+    pub fn set_next(&self, next: *mut Self) {
+        self.next.store(next, Ordering::Release);
+    }
+
+    /// Returns a pointer to the slot at position `idx`, which must be within the fragment.
+    #[inline(always)]
+    pub fn ptr(&self, idx: usize) -> *mut T {
+        debug_assert!(self.contains(idx));
+        self.slots[idx - self.begin_idx].get().cast()
+    }
+
+    /// Returns a pointer to the slice of slots at positions `range`, which must be a non-empty range within the fragment.
+    pub fn slice_ptr(&self, range: Range<usize>) -> *mut [T] {
+        debug_assert!(
+            self.contains(range.start) && range.start < range.end && range.end <= self.end_idx()
+        );
+        ptr::slice_from_raw_parts_mut(self.ptr(range.start), range.end - range.start)
+    }
+}
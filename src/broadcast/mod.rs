@@ -0,0 +1,6 @@
+mod fragment;
+mod queue;
+mod subscriber;
+
+pub use queue::BroadcastQueue;
+pub use subscriber::Subscriber;
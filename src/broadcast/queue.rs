@@ -0,0 +1,487 @@
+use crate::{
+    atomic_utils::comp_exch_weak,
+    broadcast::{Subscriber, fragment::Fragment},
+    lock_utils::lock_ignoring_poison,
+};
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+/// Number of elements a subscriber reads between two attempts to reclaim the elements which all subscribers have passed.
+pub(super) const RECLAIM_PERIOD: usize = 1024;
+
+/// Default capacity of each fragment of the broadcast queue.
+const DEFAULT_FRAGMENT_CAPACITY: usize = 1024;
+
+/// Cursor of a subscriber which is no longer alive.
+const INACTIVE: usize = usize::MAX;
+
+/// A thread safe broadcast queue where every subscriber receives a reference to every element pushed after it subscribed.
+///
+/// * Producers [`push`] and [`extend`] with a shared reference, exactly as they do with a [`ConcurrentQueue`].
+/// * Each [`Subscriber`] owns its read cursor and independently receives `&T` for all elements pushed since it subscribed.
+///   Elements are never cloned to be fanned out to multiple consumers.
+///
+/// Elements are stored in a chain of fixed capacity fragments and they are never moved;
+/// therefore, references handed out to the subscribers remain valid while the storage grows.
+///
+/// Once all subscribers have passed an element, it is no longer reachable and it is dropped, releasing the resources it owns.
+/// Further, once all subscribers have passed a fragment, the fragment itself is freed.
+/// Therefore, memory usage of a long running broadcast is bounded by the distance between the slowest subscriber and the producers.
+/// Reclamation is performed periodically by the subscribers as they move forward, and it can be triggered by [`reclaim`].
+///
+/// [`push`]: crate::BroadcastQueue::push
+/// [`extend`]: crate::BroadcastQueue::extend
+/// [`reclaim`]: crate::BroadcastQueue::reclaim
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+/// [`Subscriber`]: crate::Subscriber
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::BroadcastQueue;
+///
+/// let queue = BroadcastQueue::new();
+///
+/// queue.push('a');
+///
+/// let mut s1 = queue.subscribe();
+/// queue.extend(['b', 'c']);
+///
+/// let mut s2 = queue.subscribe();
+/// queue.push('d');
+///
+/// assert_eq!(s1.next(), Some(&'b'));
+/// assert_eq!(s1.next(), Some(&'c'));
+/// assert_eq!(s1.next(), Some(&'d'));
+/// assert_eq!(s1.next(), None);
+///
+/// assert_eq!(s2.next(), Some(&'d'));
+/// assert_eq!(s2.next(), None);
+/// ```
+///
+/// The following example fans out events to several independent pipelines running concurrently with the producer.
+///
+/// ```
+/// use orx_concurrent_queue::BroadcastQueue;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// let queue = BroadcastQueue::new();
+/// let completed = AtomicBool::new(false);
+///
+/// let subscribers: Vec<_> = (0..3).map(|_| queue.subscribe()).collect();
+///
+/// std::thread::scope(|s| {
+///     let handles: Vec<_> = subscribers
+///         .into_iter()
+///         .map(|mut subscriber| {
+///             let completed = &completed;
+///             s.spawn(move || {
+///                 let mut sum = 0;
+///                 loop {
+///                     let done = completed.load(Ordering::Acquire);
+///                     while let Some(x) = subscriber.next() {
+///                         sum += *x;
+///                     }
+///                     if done {
+///                         break sum;
+///                     }
+///                 }
+///             })
+///         })
+///         .collect();
+///
+///     for i in 0..1000 {
+///         queue.push(i);
+///     }
+///     completed.store(true, Ordering::Release);
+///
+///     for h in handles {
+///         assert_eq!(h.join().unwrap(), 999 * 1000 / 2);
+///     }
+/// });
+/// ```
+pub struct BroadcastQueue<T>
+where
+    T: Send + Sync,
+{
+    fragment_capacity: usize,
+    write_reserved: AtomicUsize,
+    written: AtomicUsize,
+    num_dropped: AtomicUsize,
+    /// Last allocated fragment.
+    tail: AtomicPtr<Fragment<T>>,
+    /// End position of the last allocated fragment, which is updated after the `tail`.
+    capacity: AtomicUsize,
+    state: Mutex<State<T>>,
+    phantom: PhantomData<T>,
+}
+
+/// State of the broadcast queue which is updated under the lock.
+struct State<T> {
+    cursors: Vec<Arc<AtomicUsize>>,
+    /// First fragment which is not freed yet.
+    head: *mut Fragment<T>,
+}
+
+unsafe impl<T> Send for BroadcastQueue<T> where T: Send + Sync {}
+
+unsafe impl<T> Sync for BroadcastQueue<T> where T: Send + Sync {}
+
+impl<T> Drop for BroadcastQueue<T>
+where
+    T: Send + Sync,
+{
+    fn drop(&mut self) {
+        let begin_idx = self.num_dropped.load(Ordering::Relaxed);
+        let end_idx = self.written.load(Ordering::Acquire);
+        let state = self.state.get_mut().unwrap_or_else(PoisonError::into_inner);
+        // SAFETY: no subscriber is alive; hence, all remaining elements can be dropped and all fragments can be freed
+        unsafe { drop_elements(state.head, begin_idx, end_idx) };
+        let mut fragment = state.head;
+        while !fragment.is_null() {
+            let next = unsafe { &*fragment }.next();
+            unsafe { Fragment::free(fragment) };
+            fragment = next;
+        }
+    }
+}
+
+impl<T> Default for BroadcastQueue<T>
+where
+    T: Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BroadcastQueue<T>
+where
+    T: Send + Sync,
+{
+    /// Creates a new empty broadcast queue with the default fragment capacity of 1024 elements.
+    pub fn new() -> Self {
+        Self::with_fragment_capacity(DEFAULT_FRAGMENT_CAPACITY)
+    }
+
+    /// Creates a new empty broadcast queue where the elements are stored in fragments of `fragment_capacity` elements.
+    ///
+    /// A fragment is freed once all subscribers have passed all of its elements.
+    /// Smaller fragments release memory sooner, while larger fragments require fewer allocations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fragment_capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::BroadcastQueue;
+    ///
+    /// let queue = BroadcastQueue::with_fragment_capacity(4);
+    /// let mut subscriber = queue.subscribe();
+    ///
+    /// queue.extend(0..10);
+    /// assert_eq!(queue.capacity(), 12);
+    ///
+    /// for _ in 0..10 {
+    ///     _ = subscriber.next();
+    /// }
+    /// _ = subscriber.next();
+    ///
+    /// // first two fragments are freed; the last one is still in use
+    /// _ = queue.reclaim();
+    /// assert_eq!(queue.capacity(), 4);
+    /// ```
+    pub fn with_fragment_capacity(fragment_capacity: usize) -> Self {
+        assert!(fragment_capacity > 0, "fragment capacity must be positive");
+        let head = Fragment::allocate(0, fragment_capacity);
+        Self {
+            fragment_capacity,
+            write_reserved: AtomicUsize::new(0),
+            written: AtomicUsize::new(0),
+            num_dropped: AtomicUsize::new(0),
+            tail: AtomicPtr::new(head),
+            capacity: AtomicUsize::new(fragment_capacity),
+            state: Mutex::new(State {
+                cursors: Vec::new(),
+                head,
+            }),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates a new subscriber which will receive all elements pushed to the queue from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::BroadcastQueue;
+    ///
+    /// let queue = BroadcastQueue::new();
+    /// queue.push(0);
+    ///
+    /// let mut subscriber = queue.subscribe();
+    /// assert_eq!(subscriber.next(), None);
+    ///
+    /// queue.push(1);
+    /// assert_eq!(subscriber.next(), Some(&1));
+    /// ```
+    pub fn subscribe(&self) -> Subscriber<'_, T> {
+        let mut state = self.lock_state();
+        let begin_idx = self.num_written();
+        let cursor = Arc::new(AtomicUsize::new(begin_idx));
+        state.cursors.push(cursor.clone());
+        self.reclaim_with(&mut state);
+
+        // the first fragment ending at or after the written elements is allocated and it is not freed while the cursor is behind its end
+        let mut fragment = state.head;
+        while unsafe { &*fragment }.end_idx() < begin_idx {
+            fragment = unsafe { &*fragment }.next();
+        }
+        drop(state);
+
+        Subscriber::new(self, cursor, fragment, begin_idx)
+    }
+
+    /// Returns the number of alive subscribers.
+    pub fn num_subscribers(&self) -> usize {
+        let state = self.lock_state();
+        state
+            .cursors
+            .iter()
+            .filter(|x| x.load(Ordering::Relaxed) != INACTIVE)
+            .count()
+    }
+
+    // grow
+
+    /// Pushes the `value` to the back of the queue, which will be received by all current subscribers.
+    pub fn push(&self, value: T) {
+        let idx = self.write_reserved.fetch_add(1, Ordering::Relaxed);
+
+        // SAFETY: idx is reserved and not written yet
+        let fragment = unsafe { self.fragment_of(idx) };
+        unsafe { fragment.ptr(idx).write(value) };
+
+        while comp_exch_weak(&self.written, idx, idx + 1).is_err() {}
+    }
+
+    /// Extends the queue by pushing `values` elements to the back of the queue, which will be received by all current subscribers.
+    pub fn extend<I, Iter>(&self, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        let values = values.into_iter();
+        let num_items = values.len();
+        if num_items == 0 {
+            return;
+        }
+
+        let begin_idx = self.write_reserved.fetch_add(num_items, Ordering::Relaxed);
+        let end_idx = begin_idx + num_items;
+
+        // SAFETY: positions begin_idx..end_idx are reserved and they are not written until the written counter is updated
+        let mut fragment = unsafe { self.fragment_of(begin_idx) };
+        for (idx, value) in (begin_idx..end_idx).zip(values) {
+            if !fragment.contains(idx) {
+                fragment = unsafe { self.fragment_of(idx) };
+            }
+            unsafe { fragment.ptr(idx).write(value) };
+        }
+
+        while comp_exch_weak(&self.written, begin_idx, end_idx).is_err() {}
+    }
+
+    // get
+
+    /// Returns the total number of elements pushed to the queue so far.
+    ///
+    /// This is the position at which a new subscriber starts reading.
+    pub fn num_pushed(&self) -> usize {
+        self.num_written()
+    }
+
+    /// Returns the number of elements that are not yet reclaimed, which is the number of elements
+    /// that are not yet passed by all subscribers and the number of passed elements that are
+    /// waiting to be reclaimed.
+    pub fn num_retained(&self) -> usize {
+        self.num_written() - self.num_dropped.load(Ordering::Acquire)
+    }
+
+    /// Returns the total capacity of the fragments which are currently allocated.
+    ///
+    /// Fragments which are passed by all subscribers are freed by [`reclaim`]; hence, the capacity does not grow
+    /// with the number of pushed elements as long as the subscribers keep up with the producers.
+    ///
+    /// [`reclaim`]: crate::BroadcastQueue::reclaim
+    pub fn capacity(&self) -> usize {
+        let state = self.lock_state();
+        self.capacity.load(Ordering::Acquire) - unsafe { &*state.head }.begin_idx()
+    }
+
+    // reclaim
+
+    /// Drops all elements which are passed by all subscribers and frees the fragments which are passed by all subscribers;
+    /// returns the number of dropped elements.
+    ///
+    /// Elements are also periodically reclaimed by the subscribers as they move forward;
+    /// therefore, calling this method is not required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::BroadcastQueue;
+    ///
+    /// let queue = BroadcastQueue::new();
+    /// let mut s1 = queue.subscribe();
+    /// let mut s2 = queue.subscribe();
+    ///
+    /// queue.extend(0..10);
+    ///
+    /// for _ in 0..4 {
+    ///     _ = s1.next();
+    /// }
+    /// for _ in 0..7 {
+    ///     _ = s2.next();
+    /// }
+    ///
+    /// // first subscriber passed 3 elements, it still holds the reference of the 4th
+    /// assert_eq!(queue.reclaim(), 3);
+    /// assert_eq!(queue.num_retained(), 7);
+    ///
+    /// drop(s1);
+    ///
+    /// assert_eq!(queue.reclaim(), 3);
+    /// assert_eq!(queue.num_retained(), 4);
+    /// ```
+    pub fn reclaim(&self) -> usize {
+        let mut state = self.lock_state();
+        self.reclaim_with(&mut state)
+    }
+
+    // helpers
+
+    /// Reclaims elements only if no other thread is currently reclaiming or subscribing.
+    pub(super) fn try_reclaim(&self) {
+        let state = match self.state.try_lock() {
+            Ok(x) => Some(x),
+            Err(TryLockError::Poisoned(x)) => Some(x.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        if let Some(mut state) = state {
+            self.reclaim_with(&mut state);
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn num_written(&self) -> usize {
+        self.written.load(Ordering::Acquire)
+    }
+
+    /// Returns the fragment containing the position `idx`, allocating the fragments up to it if necessary.
+    ///
+    /// # Safety
+    ///
+    /// The position `idx` must be reserved and not written yet, and the returned fragment must not be used
+    /// after the position is written.
+    /// A fragment ending after a position which is not written is never freed, since no subscriber can pass it.
+    unsafe fn fragment_of(&self, idx: usize) -> &Fragment<T> {
+        // the tail is updated before the capacity; hence, the tail ends after idx and it is not freed
+        if idx < self.capacity.load(Ordering::Acquire) {
+            let tail = unsafe { &*self.tail.load(Ordering::Acquire) };
+            if tail.contains(idx) {
+                return tail;
+            }
+        }
+
+        let state = self.lock_state();
+        // fragments are freed only under the lock; the head begins at or before the dropped elements, which are written, and hence, before idx
+        let tail = unsafe { &*self.tail.load(Ordering::Acquire) };
+        let mut fragment = match tail.begin_idx() <= idx {
+            true => tail,
+            false => unsafe { &*state.head },
+        };
+        while !fragment.contains(idx) {
+            let next = match fragment.next() {
+                next if next.is_null() => {
+                    let next = Fragment::allocate(fragment.end_idx(), self.fragment_capacity);
+                    fragment.set_next(next);
+                    self.tail.store(next, Ordering::Release);
+                    self.capacity
+                        .store(unsafe { &*next }.end_idx(), Ordering::Release);
+                    next
+                }
+                next => next,
+            };
+            fragment = unsafe { &*next };
+        }
+        fragment
+    }
+
+    fn reclaim_with(&self, state: &mut State<T>) -> usize {
+        state
+            .cursors
+            .retain(|x| x.load(Ordering::Relaxed) != INACTIVE);
+
+        let end_idx = match state
+            .cursors
+            .iter()
+            .map(|x| x.load(Ordering::Acquire))
+            .min()
+        {
+            Some(min_cursor) => min_cursor,
+            None => self.num_written(),
+        };
+
+        // the state is protected by the lock; hence, this is the only thread dropping elements and freeing fragments
+        let begin_idx = self.num_dropped.load(Ordering::Relaxed);
+        if end_idx <= begin_idx {
+            return 0;
+        }
+        self.num_dropped.store(end_idx, Ordering::Release);
+        // SAFETY: elements before the minimum cursor are written and they are no longer referenced by any subscriber
+        unsafe { drop_elements(state.head, begin_idx, end_idx) };
+
+        // a subscriber might still hold the fragment ending at its cursor; hence, only fragments ending before all cursors are freed
+        while unsafe { &*state.head }.end_idx() < end_idx {
+            let next = unsafe { &*state.head }.next();
+            unsafe { Fragment::free(state.head) };
+            state.head = next;
+        }
+
+        end_idx - begin_idx
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, State<T>> {
+        lock_ignoring_poison(&self.state)
+    }
+}
+
+/// Drops the elements at positions `begin_idx..end_idx` which are stored in the fragments starting from `fragment`.
+///
+/// # Safety
+///
+/// All positions must be written and not yet dropped, and `fragment` must begin at or before `begin_idx`.
+unsafe fn drop_elements<T>(mut fragment: *mut Fragment<T>, begin_idx: usize, end_idx: usize) {
+    let mut idx = begin_idx;
+    while idx < end_idx {
+        let current = unsafe { &*fragment };
+        let end = end_idx.min(current.end_idx());
+        if idx < end {
+            unsafe { current.slice_ptr(idx..end).drop_in_place() };
+            idx = end;
+        }
+        fragment = current.next();
+    }
+}
+
+/// Sets the cursor of a subscriber which is being dropped as inactive.
+pub(super) fn deactivate(cursor: &AtomicUsize) {
+    cursor.store(INACTIVE, Ordering::Release);
+}
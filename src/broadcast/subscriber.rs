@@ -0,0 +1,142 @@
+use crate::{
+    BroadcastQueue,
+    broadcast::{
+        fragment::Fragment,
+        queue::{RECLAIM_PERIOD, deactivate},
+    },
+};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A subscriber of a [`BroadcastQueue`] which owns a read cursor and receives a reference to every element
+/// pushed to the queue after it subscribed.
+///
+/// Subscribers are independent of each other; each subscriber moves forward at its own pace.
+///
+/// The subscriber is created by [`BroadcastQueue::subscribe`] and it unsubscribes when dropped.
+///
+/// [`BroadcastQueue`]: crate::BroadcastQueue
+/// [`BroadcastQueue::subscribe`]: crate::BroadcastQueue::subscribe
+pub struct Subscriber<'a, T>
+where
+    T: Send + Sync,
+{
+    queue: &'a BroadcastQueue<T>,
+    cursor: Arc<AtomicUsize>,
+    /// Fragment containing the last received element; or the fragment ending at or after the position
+    /// at which the subscriber started reading. It is not freed since it ends at or after the cursor.
+    fragment: *const Fragment<T>,
+    released: usize,
+    next_idx: usize,
+}
+
+unsafe impl<T> Send for Subscriber<'_, T> where T: Send + Sync {}
+
+unsafe impl<T> Sync for Subscriber<'_, T> where T: Send + Sync {}
+
+impl<T> Drop for Subscriber<'_, T>
+where
+    T: Send + Sync,
+{
+    fn drop(&mut self) {
+        deactivate(&self.cursor);
+    }
+}
+
+impl<'a, T> Subscriber<'a, T>
+where
+    T: Send + Sync,
+{
+    pub(super) fn new(
+        queue: &'a BroadcastQueue<T>,
+        cursor: Arc<AtomicUsize>,
+        fragment: *const Fragment<T>,
+        begin_idx: usize,
+    ) -> Self {
+        Self {
+            queue,
+            cursor,
+            fragment,
+            released: begin_idx,
+            next_idx: begin_idx,
+        }
+    }
+
+    /// Returns a reference to the next element that this subscriber has not received yet;
+    /// returns None if the subscriber has received all elements pushed so far.
+    ///
+    /// Once this method is called again, the previously returned element is released by this subscriber
+    /// and it can be reclaimed once all other subscribers release it as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::BroadcastQueue;
+    ///
+    /// let queue = BroadcastQueue::new();
+    /// let mut subscriber = queue.subscribe();
+    ///
+    /// queue.extend([1, 2]);
+    /// assert_eq!(subscriber.next(), Some(&1));
+    /// assert_eq!(subscriber.next(), Some(&2));
+    /// assert_eq!(subscriber.next(), None);
+    ///
+    /// queue.push(3);
+    /// assert_eq!(subscriber.next(), Some(&3));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&T> {
+        let idx = self.next_idx;
+
+        // the exclusive borrow guarantees that the references returned before are no longer in use
+        if self.released < idx {
+            self.released = idx;
+            self.cursor.store(idx, Ordering::Release);
+            if idx.is_multiple_of(RECLAIM_PERIOD) {
+                self.queue.try_reclaim();
+            }
+        }
+
+        match idx < self.queue.num_written() {
+            true => {
+                // SAFETY: the fragment ends at or after the cursor; hence, it is not freed
+                let mut fragment = unsafe { &*self.fragment };
+                if !fragment.contains(idx) {
+                    // the fragment of a written element is linked before the element is written
+                    fragment = unsafe { &*fragment.next() };
+                    self.fragment = fragment;
+                }
+                self.next_idx += 1;
+                // SAFETY: the element is written and it cannot be reclaimed before this subscriber releases it
+                Some(unsafe { &*fragment.ptr(idx) })
+            }
+            false => None,
+        }
+    }
+
+    /// Returns the number of elements pushed to the queue that this subscriber has not received yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::BroadcastQueue;
+    ///
+    /// let queue = BroadcastQueue::new();
+    /// let mut subscriber = queue.subscribe();
+    ///
+    /// queue.extend(0..5);
+    /// assert_eq!(subscriber.num_pending(), 5);
+    ///
+    /// _ = subscriber.next();
+    /// assert_eq!(subscriber.num_pending(), 4);
+    /// ```
+    pub fn num_pending(&self) -> usize {
+        self.queue.num_written().saturating_sub(self.next_idx)
+    }
+
+    /// Returns the position of the next element that this subscriber will receive, which is the
+    /// number of elements pushed to the queue before it.
+    pub fn position(&self) -> usize {
+        self.next_idx
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{AllocationPolicy, ChunkPolicy, ConcurrentQueue, WaitStrategy};
+use core::marker::PhantomData;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::{Doubling, Linear, SplitVec};
+
+/// Maximum number of fragments of a split vector which can safely grow concurrently.
+#[cfg(target_pointer_width = "32")]
+const MAX_FRAGMENTS_CAPACITY: usize = 29;
+#[cfg(not(target_pointer_width = "32"))]
+const MAX_FRAGMENTS_CAPACITY: usize = 32;
+
+/// Capacity of the first fragment of a split vector with doubling growth.
+const FIRST_DOUBLING_FRAGMENT_CAPACITY: usize = 4;
+
+/// Number of fragments of a split vector with linear growth when the maximum capacity is not specified.
+const DEFAULT_LINEAR_FRAGMENTS_CAPACITY: usize = 1024;
+
+/// Backend, or the underlying storage, of a concurrent queue created by the [`ConcurrentQueueBuilder`].
+///
+/// [`ConcurrentQueueBuilder`]: crate::ConcurrentQueueBuilder
+pub trait Backend<T: Send> {
+    /// Pinned vector which is converted into the concurrent pinned vector storing the elements of the queue.
+    type PinnedVec: IntoConcurrentPinnedVec<T, ConPinnedVec = Self::ConPinnedVec>;
+
+    /// Concurrent pinned vector storing the elements of the queue.
+    type ConPinnedVec: ConcurrentPinnedVec<T>;
+
+    /// Creates an empty pinned vector which can concurrently grow up to at least `max_capacity` elements
+    /// if it is provided; or up to the default maximum capacity of the backend otherwise.
+    fn create_vec(&self, max_capacity: Option<usize>) -> Self::PinnedVec;
+}
+
+/// Backend of a queue storing its elements in a [`SplitVec`] with [`Doubling`] growth,
+/// which is the default backend of the concurrent queue.
+///
+/// Capacity of every new fragment is twice the capacity of the prior fragment.
+/// Unless a maximum capacity is specified, the queue can grow to practically unlimited capacity.
+///
+/// [`SplitVec`]: orx_split_vec::SplitVec
+/// [`Doubling`]: orx_split_vec::Doubling
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DoublingGrowth;
+
+impl<T: Send> Backend<T> for DoublingGrowth {
+    type PinnedVec = SplitVec<T, Doubling>;
+    type ConPinnedVec = <SplitVec<T, Doubling> as IntoConcurrentPinnedVec<T>>::ConPinnedVec;
+
+    fn create_vec(&self, max_capacity: Option<usize>) -> Self::PinnedVec {
+        // capacity of f fragments is 4 * (2^f - 1)
+        let fragments_capacity = max_capacity.map(|x| {
+            (x.div_ceil(FIRST_DOUBLING_FRAGMENT_CAPACITY) + 1)
+                .checked_next_power_of_two()
+                .map(|x| x.trailing_zeros() as usize)
+                .unwrap_or(MAX_FRAGMENTS_CAPACITY)
+        });
+
+        match fragments_capacity {
+            Some(f) if f < MAX_FRAGMENTS_CAPACITY => {
+                SplitVec::with_doubling_growth_and_fragments_capacity(f.max(1))
+            }
+            _ => SplitVec::with_doubling_growth_and_max_concurrent_capacity(),
+        }
+    }
+}
+
+/// Backend of a queue storing its elements in a [`SplitVec`] with [`Linear`] growth.
+///
+/// All fragments have the same capacity, which is a power of two.
+/// Unless a maximum capacity is specified, the queue can grow up to 1024 fragments.
+///
+/// [`SplitVec`]: orx_split_vec::SplitVec
+/// [`Linear`]: orx_split_vec::Linear
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinearGrowth {
+    fragment_capacity_exponent: usize,
+}
+
+impl LinearGrowth {
+    /// Creates the linear growth backend where the capacity of each fragment is
+    /// `fragment_capacity` rounded up to the next power of two, and at least 2.
+    pub fn new(fragment_capacity: usize) -> Self {
+        let fragment_capacity_exponent = fragment_capacity
+            .checked_next_power_of_two()
+            .map(|x| x.trailing_zeros() as usize)
+            .unwrap_or(MAX_FRAGMENTS_CAPACITY - 1)
+            .clamp(1, MAX_FRAGMENTS_CAPACITY - 1);
+        Self {
+            fragment_capacity_exponent,
+        }
+    }
+
+    /// Returns the capacity of each fragment.
+    pub fn fragment_capacity(&self) -> usize {
+        1 << self.fragment_capacity_exponent
+    }
+}
+
+impl<T: Send> Backend<T> for LinearGrowth {
+    type PinnedVec = SplitVec<T, Linear>;
+    type ConPinnedVec = <SplitVec<T, Linear> as IntoConcurrentPinnedVec<T>>::ConPinnedVec;
+
+    fn create_vec(&self, max_capacity: Option<usize>) -> Self::PinnedVec {
+        let fragments_capacity = match max_capacity {
+            Some(x) => x.div_ceil(self.fragment_capacity()).max(1),
+            None => DEFAULT_LINEAR_FRAGMENTS_CAPACITY,
+        };
+        SplitVec::with_linear_growth_and_fragments_capacity(
+            self.fragment_capacity_exponent,
+            fragments_capacity,
+        )
+    }
+}
+
+/// Backend of a queue storing its elements in a [`FixedVec`], which allocates its entire capacity upfront
+/// and can never grow beyond it.
+///
+/// The fixed capacity is also the maximum capacity of the queue; it cannot be changed by a different maximum capacity.
+///
+/// [`FixedVec`]: orx_fixed_vec::FixedVec
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedCapacity {
+    capacity: usize,
+}
+
+impl<T: Send> Backend<T> for FixedCapacity {
+    type PinnedVec = FixedVec<T>;
+    type ConPinnedVec = <FixedVec<T> as IntoConcurrentPinnedVec<T>>::ConPinnedVec;
+
+    fn create_vec(&self, max_capacity: Option<usize>) -> Self::PinnedVec {
+        assert!(
+            max_capacity.is_none_or(|x| x == self.capacity),
+            "Maximum capacity of a fixed capacity queue cannot differ from its fixed capacity."
+        );
+        FixedVec::new(self.capacity)
+    }
+}
+
+/// Builder of a [`ConcurrentQueue`] which allows to configure its backend, capacity, allocation, pulling and waiting behavior.
+///
+/// The builder is created by [`ConcurrentQueue::builder`] with the default backend [`DoublingGrowth`].
+/// The backend can be changed by [`doubling_growth`], [`linear_growth`] and [`fixed_capacity`] methods,
+/// which also determine the type of the built queue.
+///
+/// The builder does not have a close option since the queue has no closed state: consumers observe the end
+/// of the elements either by an empty queue, or by a close signal of their own such as the one passed to
+/// [`par_map_ordered_until`].
+///
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+/// [`ConcurrentQueue::builder`]: crate::ConcurrentQueue::builder
+/// [`DoublingGrowth`]: crate::DoublingGrowth
+/// [`doubling_growth`]: crate::ConcurrentQueueBuilder::doubling_growth
+/// [`linear_growth`]: crate::ConcurrentQueueBuilder::linear_growth
+/// [`fixed_capacity`]: crate::ConcurrentQueueBuilder::fixed_capacity
+/// [`par_map_ordered_until`]: crate::ConcurrentQueue::par_map_ordered_until
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::{ChunkPolicy, ConcurrentQueue};
+///
+/// // default backend
+/// let queue = ConcurrentQueue::builder().initial_capacity(100).build();
+/// queue.extend(0..100);
+/// assert_eq!(queue.len(), 100);
+///
+/// // split vec with linear growth which can hold up to 1000 elements in fragments of 64 elements
+/// let queue = ConcurrentQueue::builder()
+///     .linear_growth(64)
+///     .max_capacity(1000)
+///     .chunk_policy(ChunkPolicy::guided(4))
+///     .build();
+/// queue.extend(0..1000);
+/// assert_eq!(queue.pull_adaptive().map(|x| x.len()), Some(250));
+///
+/// // fixed capacity vec which can hold up to 1000 elements
+/// let queue = ConcurrentQueue::builder().fixed_capacity(1000).build();
+/// queue.push('x');
+/// assert_eq!(queue.pop(), Some('x'));
+/// ```
+pub struct ConcurrentQueueBuilder<T, B = DoublingGrowth>
+where
+    T: Send,
+    B: Backend<T>,
+{
+    backend: B,
+    initial_capacity: usize,
+    max_capacity: Option<usize>,
+    allocation_policy: AllocationPolicy,
+    chunk_policy: ChunkPolicy,
+    pull_metrics: bool,
+    wait_strategy: WaitStrategy,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Default for ConcurrentQueueBuilder<T, DoublingGrowth>
+where
+    T: Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ConcurrentQueueBuilder<T, DoublingGrowth>
+where
+    T: Send,
+{
+    /// Creates a new builder with the default backend [`DoublingGrowth`].
+    ///
+    /// [`DoublingGrowth`]: crate::DoublingGrowth
+    pub fn new() -> Self {
+        Self {
+            backend: DoublingGrowth,
+            initial_capacity: 0,
+            max_capacity: None,
+            allocation_policy: AllocationPolicy::default(),
+            chunk_policy: ChunkPolicy::default(),
+            pull_metrics: true,
+            wait_strategy: WaitStrategy::default(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, B> ConcurrentQueueBuilder<T, B>
+where
+    T: Send,
+    B: Backend<T>,
+{
+    fn with_backend<B2: Backend<T>>(self, backend: B2) -> ConcurrentQueueBuilder<T, B2> {
+        ConcurrentQueueBuilder {
+            backend,
+            initial_capacity: self.initial_capacity,
+            max_capacity: self.max_capacity,
+            allocation_policy: self.allocation_policy,
+            chunk_policy: self.chunk_policy,
+            pull_metrics: self.pull_metrics,
+            wait_strategy: self.wait_strategy,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Uses a [`SplitVec`] with [`Doubling`] growth as the backend, see [`DoublingGrowth`].
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Doubling`]: orx_split_vec::Doubling
+    /// [`DoublingGrowth`]: crate::DoublingGrowth
+    pub fn doubling_growth(self) -> ConcurrentQueueBuilder<T, DoublingGrowth> {
+        self.with_backend(DoublingGrowth)
+    }
+
+    /// Uses a [`SplitVec`] with [`Linear`] growth as the backend, where the capacity of each fragment is
+    /// `fragment_capacity` rounded up to the next power of two; see [`LinearGrowth`].
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Linear`]: orx_split_vec::Linear
+    /// [`LinearGrowth`]: crate::LinearGrowth
+    pub fn linear_growth(
+        self,
+        fragment_capacity: usize,
+    ) -> ConcurrentQueueBuilder<T, LinearGrowth> {
+        self.with_backend(LinearGrowth::new(fragment_capacity))
+    }
+
+    /// Uses a [`FixedVec`] with the given `capacity` as the backend; see [`FixedCapacity`].
+    ///
+    /// This is equivalent to setting the [`max_capacity`] of the queue to `capacity`.
+    /// Setting a different maximum capacity afterwards is an error, which panics when the queue is built.
+    ///
+    /// [`FixedVec`]: orx_fixed_vec::FixedVec
+    /// [`FixedCapacity`]: crate::FixedCapacity
+    /// [`max_capacity`]: crate::ConcurrentQueueBuilder::max_capacity
+    pub fn fixed_capacity(mut self, capacity: usize) -> ConcurrentQueueBuilder<T, FixedCapacity> {
+        self.max_capacity = Some(capacity);
+        self.with_backend(FixedCapacity { capacity })
+    }
+
+    /// Sets the initial capacity of the queue, which is allocated when the queue is built.
+    ///
+    /// The queue does not need to grow while pushing the first `initial_capacity` elements.
+    pub fn initial_capacity(mut self, initial_capacity: usize) -> Self {
+        self.initial_capacity = initial_capacity;
+        self
+    }
+
+    /// Sets the maximum capacity of the queue.
+    ///
+    /// The built queue can hold at least `max_capacity` elements.
+    /// The exact maximum capacity might be greater than `max_capacity` depending on the fragment capacities of the backend.
+    /// The queue panics if the total number of elements pushed to the queue exceeds its maximum capacity.
+    ///
+    /// The maximum capacity of a queue with the [`fixed_capacity`] backend is its fixed capacity, which cannot be changed.
+    ///
+    /// [`fixed_capacity`]: crate::ConcurrentQueueBuilder::fixed_capacity
+    pub fn max_capacity(mut self, max_capacity: usize) -> Self {
+        self.max_capacity = Some(max_capacity);
+        self
+    }
+
+    /// Sets the allocation policy determining when the queue allocates new capacity; see [`AllocationPolicy`].
+    ///
+    /// [`AllocationPolicy`]: crate::AllocationPolicy
+    pub fn allocation_policy(mut self, allocation_policy: AllocationPolicy) -> Self {
+        self.allocation_policy = allocation_policy;
+        self
+    }
+
+    /// Sets the chunk policy used by [`pull_adaptive`].
+    ///
+    /// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+    pub fn chunk_policy(mut self, chunk_policy: ChunkPolicy) -> Self {
+        self.chunk_policy = chunk_policy;
+        self
+    }
+
+    /// Sets whether or not the queue collects the [`pull_metrics`]; they are collected by default.
+    ///
+    /// [`pull_metrics`]: crate::ConcurrentQueue::pull_metrics
+    pub fn pull_metrics(mut self, enabled: bool) -> Self {
+        self.pull_metrics = enabled;
+        self
+    }
+
+    /// Sets the wait strategy used by [`pull_batch`] while waiting for the batch to fill; see [`WaitStrategy`].
+    ///
+    /// [`pull_batch`]: crate::ConcurrentQueue::pull_batch
+    /// [`WaitStrategy`]: crate::WaitStrategy
+    pub fn wait_strategy(mut self, wait_strategy: WaitStrategy) -> Self {
+        self.wait_strategy = wait_strategy;
+        self
+    }
+
+    /// Builds the empty concurrent queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the initial capacity is greater than the maximum capacity.
+    ///
+    /// Panics if the backend is [`fixed_capacity`] and the maximum capacity is set to a different value afterwards.
+    ///
+    /// [`fixed_capacity`]: crate::ConcurrentQueueBuilder::fixed_capacity
+    pub fn build(self) -> ConcurrentQueue<T, B::ConPinnedVec> {
+        if let Some(max_capacity) = self.max_capacity {
+            assert!(
+                self.initial_capacity <= max_capacity,
+                "Initial capacity cannot exceed the maximum capacity."
+            );
+        }
+
+        let vec = self.backend.create_vec(self.max_capacity);
+        let queue = ConcurrentQueue::from(vec)
+            .with_allocation_policy(self.allocation_policy)
+            .with_chunk_policy(self.chunk_policy)
+            .with_pull_metrics(self.pull_metrics)
+            .with_wait_strategy(self.wait_strategy);
+
+        if self.initial_capacity > 0 {
+            queue.grow_to(self.initial_capacity);
+        }
+
+        queue
+    }
+}
@@ -0,0 +1,163 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Policy determining the chunk sizes of [`pull_adaptive`] calls.
+///
+/// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::ChunkPolicy;
+///
+/// let guided = ChunkPolicy::guided(4);
+/// assert_eq!(guided.chunk_size(1000), 250);
+/// assert_eq!(guided.chunk_size(6), 2);
+/// assert_eq!(guided.chunk_size(0), 1);
+///
+/// let fixed = ChunkPolicy::Fixed(64);
+/// assert_eq!(fixed.chunk_size(1000), 64);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkPolicy {
+    /// Every pull requests the same number of elements.
+    Fixed(usize),
+    /// Guided scheduling, similar to OpenMP's `guided` schedule: every pull requests the number of remaining
+    /// elements divided by the number of consumers, clamped to `min_chunk_size..=max_chunk_size`.
+    ///
+    /// Chunks are large while the queue is long, reducing the contention on the shared counters;
+    /// and they get smaller towards the tail, so that the remaining work is evenly spread over the consumers.
+    Guided {
+        /// Number of consumers concurrently pulling from the queue.
+        num_consumers: usize,
+        /// Lower bound of the chunk size.
+        min_chunk_size: usize,
+        /// Upper bound of the chunk size.
+        max_chunk_size: usize,
+    },
+}
+
+impl Default for ChunkPolicy {
+    /// Guided scheduling for 8 consumers with chunk sizes between 1 and 1024.
+    fn default() -> Self {
+        Self::Guided {
+            num_consumers: 8,
+            min_chunk_size: 1,
+            max_chunk_size: 1024,
+        }
+    }
+}
+
+impl ChunkPolicy {
+    /// Creates a guided policy for the given number of consumers without any bounds on the chunk size.
+    pub fn guided(num_consumers: usize) -> Self {
+        Self::Guided {
+            num_consumers,
+            min_chunk_size: 1,
+            max_chunk_size: usize::MAX,
+        }
+    }
+
+    /// Returns the chunk size to be requested when the queue has `len` elements, which is always positive.
+    pub fn chunk_size(&self, len: usize) -> usize {
+        let chunk_size = match *self {
+            Self::Fixed(chunk_size) => chunk_size,
+            Self::Guided {
+                num_consumers,
+                min_chunk_size,
+                max_chunk_size,
+            } => len
+                .div_ceil(num_consumers.max(1))
+                .min(max_chunk_size)
+                .max(min_chunk_size),
+        };
+        chunk_size.max(1)
+    }
+}
+
+/// Statistics of the [`pull_adaptive`] calls on a queue, which can be used to tune the [`ChunkPolicy`].
+///
+/// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+/// [`ChunkPolicy`]: crate::ChunkPolicy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PullMetrics {
+    /// Number of pulls which returned a chunk.
+    pub num_pulls: usize,
+    /// Number of pulls which returned None since the queue was empty.
+    pub num_empty_pulls: usize,
+    /// Total number of pulled elements.
+    pub num_pulled: usize,
+    /// Size of the largest pulled chunk.
+    pub max_chunk_size: usize,
+}
+
+impl PullMetrics {
+    /// Returns the average size of the pulled chunks; returns None if no chunk is pulled.
+    pub fn average_chunk_size(&self) -> Option<f64> {
+        match self.num_pulls {
+            0 => None,
+            n => Some(self.num_pulled as f64 / n as f64),
+        }
+    }
+}
+
+/// Atomic counters collecting the [`PullMetrics`]; nothing is recorded unless the counters are enabled.
+pub(crate) struct PullCounters {
+    enabled: bool,
+    num_pulls: AtomicUsize,
+    num_empty_pulls: AtomicUsize,
+    num_pulled: AtomicUsize,
+    max_chunk_size: AtomicUsize,
+}
+
+impl Default for PullCounters {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl PullCounters {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            num_pulls: 0.into(),
+            num_empty_pulls: 0.into(),
+            num_pulled: 0.into(),
+            max_chunk_size: 0.into(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn record(&self, chunk_len: Option<usize>) {
+        if !self.enabled {
+            return;
+        }
+
+        match chunk_len {
+            Some(len) => {
+                _ = self.num_pulls.fetch_add(1, Ordering::Relaxed);
+                _ = self.num_pulled.fetch_add(len, Ordering::Relaxed);
+                _ = self.max_chunk_size.fetch_max(len, Ordering::Relaxed);
+            }
+            None => _ = self.num_empty_pulls.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> PullMetrics {
+        PullMetrics {
+            num_pulls: self.num_pulls.load(Ordering::Relaxed),
+            num_empty_pulls: self.num_empty_pulls.load(Ordering::Relaxed),
+            num_pulled: self.num_pulled.load(Ordering::Relaxed),
+            max_chunk_size: self.max_chunk_size.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        self.num_pulls.store(0, Ordering::Relaxed);
+        self.num_empty_pulls.store(0, Ordering::Relaxed);
+        self.num_pulled.store(0, Ordering::Relaxed);
+        self.max_chunk_size.store(0, Ordering::Relaxed);
+    }
+}
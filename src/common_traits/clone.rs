@@ -0,0 +1,18 @@
+use crate::ConcurrentQueue;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+impl<T, P> Clone for ConcurrentQueue<T, P>
+where
+    T: Send + Sync + Clone,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Creates a queue containing clones of the elements which are committed and not yet popped.
+    ///
+    /// The clone uses the same kind of storage and the same policies as this queue.
+    /// Pull metrics of the clone start from zero.
+    fn clone(&self) -> Self {
+        let queue = self.empty_like();
+        self.read_pending(|iter| queue.extend(iter.cloned()));
+        queue
+    }
+}
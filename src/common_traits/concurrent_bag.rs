@@ -0,0 +1,53 @@
+use crate::ConcurrentQueue;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::prelude::PseudoDefault;
+
+impl<T, P> ConcurrentQueue<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Converts the `bag` into a queue containing the elements of the bag in order, reusing the underlying
+    /// pinned vector of the bag without moving the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_bag::ConcurrentBag;
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let bag = ConcurrentBag::new();
+    /// bag.push('a');
+    /// bag.push('b');
+    ///
+    /// let queue = ConcurrentQueue::from_bag(bag);
+    /// assert_eq!(queue.pop(), Some('a'));
+    /// queue.push('c');
+    ///
+    /// let bag: ConcurrentBag<_> = queue.into();
+    /// assert_eq!(bag.into_inner(), vec!['b', 'c']);
+    /// ```
+    pub fn from_bag<Q>(bag: ConcurrentBag<T, Q>) -> Self
+    where
+        Q: IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+    {
+        bag.into_inner().into()
+    }
+}
+
+impl<T, P> From<ConcurrentQueue<T, P>> for ConcurrentBag<T, P::P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+    P::P: PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+{
+    /// Converts the queue into a bag containing the elements in the queue in order, reusing the underlying
+    /// pinned vector of the queue.
+    ///
+    /// Elements are not moved unless some of the elements are already popped, in which case the remaining
+    /// elements are moved to the front of the storage; see [`ConcurrentQueue::into_inner`].
+    fn from(queue: ConcurrentQueue<T, P>) -> Self {
+        queue.into_inner().into()
+    }
+}
@@ -0,0 +1,58 @@
+use crate::ConcurrentQueue;
+use orx_concurrent_vec::{ConcurrentElement, ConcurrentVec};
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::prelude::PseudoDefault;
+
+impl<T, P> ConcurrentQueue<ConcurrentElement<T>, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<ConcurrentElement<T>>,
+{
+    /// Converts the concurrent `vec` into a queue containing the elements of the vec in order, reusing the
+    /// underlying pinned vector of the vec without moving the elements.
+    ///
+    /// Since the storage of a [`ConcurrentVec`] holds its elements wrapped in [`ConcurrentElement`], elements of
+    /// the queue remain wrapped; their values can be accessed by the methods of the element such as `cloned`
+    /// or `map`.
+    ///
+    /// [`ConcurrentVec`]: orx_concurrent_vec::ConcurrentVec
+    /// [`ConcurrentElement`]: orx_concurrent_vec::ConcurrentElement
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use orx_concurrent_vec::ConcurrentVec;
+    ///
+    /// let vec = ConcurrentVec::new();
+    /// vec.extend(['a', 'b', 'c']);
+    ///
+    /// let queue = ConcurrentQueue::from_concurrent_vec(vec);
+    /// assert_eq!(queue.pop().map(|x| x.cloned()), Some('a'));
+    ///
+    /// let vec: ConcurrentVec<_> = queue.into();
+    /// assert_eq!(vec.clone_to_vec(), vec!['b', 'c']);
+    /// ```
+    pub fn from_concurrent_vec<Q>(vec: ConcurrentVec<T, Q>) -> Self
+    where
+        Q: IntoConcurrentPinnedVec<ConcurrentElement<T>, ConPinnedVec = P>,
+    {
+        vec.into_inner().into()
+    }
+}
+
+impl<T, P> From<ConcurrentQueue<ConcurrentElement<T>, P>> for ConcurrentVec<T, P::P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<ConcurrentElement<T>>,
+    P::P: PseudoDefault + IntoConcurrentPinnedVec<ConcurrentElement<T>, ConPinnedVec = P>,
+{
+    /// Converts the queue into a concurrent vec containing the elements in the queue in order, reusing the
+    /// underlying pinned vector of the queue.
+    ///
+    /// Elements are not moved unless some of the elements are already popped, in which case the remaining
+    /// elements are moved to the front of the storage; see [`ConcurrentQueue::into_inner`].
+    fn from(queue: ConcurrentQueue<ConcurrentElement<T>, P>) -> Self {
+        queue.into_inner().into()
+    }
+}
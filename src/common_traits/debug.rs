@@ -0,0 +1,26 @@
+use crate::ConcurrentQueue;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::sync::atomic::Ordering;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+impl<T, P> Debug for ConcurrentQueue<T, P>
+where
+    T: Send + Sync + Debug,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let written = self.num_written();
+        let write_reserved = self.num_write_reserved(Ordering::Relaxed);
+        let popped = self.num_popped();
+        self.read_pending(|iter| {
+            let elements: Vec<_> = iter.collect();
+            f.debug_struct("ConcurrentQueue")
+                .field("written", &written)
+                .field("write_reserved", &write_reserved)
+                .field("popped", &popped)
+                .field("elements", &elements)
+                .finish()
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ConcurrentQueue;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+impl<T, P, Q> PartialEq<ConcurrentQueue<T, Q>> for ConcurrentQueue<T, P>
+where
+    T: Send + Sync + PartialEq,
+    P: ConcurrentPinnedVec<T>,
+    Q: ConcurrentPinnedVec<T>,
+{
+    /// Returns true if both queues contain equal elements in the same order among the elements which are
+    /// committed and not yet popped.
+    ///
+    /// Underlying storages, policies and the number of already popped elements are not compared.
+    fn eq(&self, other: &ConcurrentQueue<T, Q>) -> bool {
+        match core::ptr::addr_eq(self, other) {
+            // a single read; otherwise, the two reads might observe different elements
+            true => self.read_pending(|mut a| a.all(|x| x.eq(x))),
+            false => self.read_pending(|a| other.read_pending(|b| a.eq(b))),
+        }
+    }
+}
+
+impl<T, P> Eq for ConcurrentQueue<T, P>
+where
+    T: Send + Sync + Eq,
+    P: ConcurrentPinnedVec<T>,
+{
+}
@@ -0,0 +1,44 @@
+use crate::{ConcurrentQueue, DefaultConPinnedVec};
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+impl<T, P> Extend<T> for ConcurrentQueue<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Pushes all elements of the iterator to the back of the queue.
+    ///
+    /// Unlike the inherent [`extend`] method, the iterator is not required to be an exact size iterator.
+    ///
+    /// [`extend`]: crate::ConcurrentQueue::extend
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, T, P> Extend<&'a T> for ConcurrentQueue<T, P>
+where
+    T: Send + Copy + 'a,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(*x);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ConcurrentQueue<T, DefaultConPinnedVec<T>>
+where
+    T: Send,
+{
+    /// Creates a queue backed by the default concurrent pinned vector, containing the elements of the
+    /// iterator in order.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        Extend::extend(&mut queue, iter);
+        queue
+    }
+}
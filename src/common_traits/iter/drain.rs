@@ -0,0 +1,117 @@
+use crate::ConcurrentQueue;
+use core::ops::Range;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// A draining iterator over a range of elements of the concurrent queue, created by [`ConcurrentQueue::drain`].
+///
+/// Elements of the range which are not yielded are dropped together with the iterator.
+/// Afterwards, the remaining elements are moved to close the gap.
+///
+/// [`ConcurrentQueue::drain`]: crate::ConcurrentQueue::drain
+pub struct QueueDrain<'a, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: &'a mut ConcurrentQueue<T, P>,
+    iter: Range<usize>,
+    drained: Range<usize>,
+    end: usize,
+}
+
+impl<'a, T, P> QueueDrain<'a, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Creates the drain over absolute positions `drained` of the `queue`, which must be within its valid range.
+    pub(crate) fn new(queue: &'a mut ConcurrentQueue<T, P>, drained: Range<usize>) -> Self {
+        let valid = queue.valid_range();
+        debug_assert!(valid.start <= drained.start && drained.end <= valid.end);
+
+        // elements from `drained.start` are leaked rather than double dropped if the drain is forgotten
+        queue.set_valid_range(valid.start..drained.start);
+
+        Self {
+            queue,
+            iter: drained.clone(),
+            drained,
+            end: valid.end,
+        }
+    }
+}
+
+impl<T, P> Iterator for QueueDrain<'_, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: each position of the drained range is written and is read only once
+        self.iter
+            .next()
+            .map(|idx| unsafe { self.queue.ptr(idx).read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, P> DoubleEndedIterator for QueueDrain<'_, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // SAFETY: each position of the drained range is written and is read only once
+        self.iter
+            .next_back()
+            .map(|idx| unsafe { self.queue.ptr(idx).read() })
+    }
+}
+
+impl<T, P> ExactSizeIterator for QueueDrain<'_, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<T, P> Drop for QueueDrain<'_, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn drop(&mut self) {
+        for idx in self.iter.clone() {
+            // SAFETY: remaining positions of the drained range are written and not yet read
+            unsafe { self.queue.ptr(idx).drop_in_place() };
+        }
+
+        let popped = self.queue.valid_range().start;
+        match self.drained.start == popped {
+            // draining from the front is equivalent to popping
+            true => self.queue.set_valid_range(self.drained.end..self.end),
+            false => {
+                let num_tail = self.end - self.drained.end;
+                for i in 0..num_tail {
+                    // SAFETY: tail elements are moved to positions which are already read or dropped
+                    unsafe {
+                        let src = self.queue.ptr(self.drained.end + i);
+                        self.queue.ptr(self.drained.start + i).write(src.read());
+                    }
+                }
+                self.queue
+                    .set_valid_range(popped..self.drained.start + num_tail);
+            }
+        }
+    }
+}
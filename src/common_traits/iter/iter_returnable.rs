@@ -0,0 +1,155 @@
+use crate::ConcurrentQueue;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// An iterator over owned elements of a chunk pulled from the concurrent queue, which pushes the elements
+/// that are not consumed back to the queue when dropped.
+///
+/// Unlike [`QueueIterOwned`], which drops the remaining elements, this iterator never destroys work
+/// when the consumer stops early due to a shutdown, an error or a cancellation.
+/// The remaining elements are pushed to the back of the queue; hence, they are popped after the elements
+/// which are already in the queue.
+/// If the queue reached its maximum capacity and cannot hold the remaining elements, the destructor has no way
+/// to hand them back and drops them. In order to recover the elements in this case, the chunk can be explicitly
+/// returned by [`return_remaining`], which gives back the chunk whenever the queue cannot hold its elements.
+///
+/// The remaining elements can be explicitly dropped instead by [`drop_remaining`].
+///
+/// The iterator is created by [`pull_returnable`].
+///
+/// [`QueueIterOwned`]: crate::iter::QueueIterOwned
+/// [`return_remaining`]: crate::iter::QueueIterReturnable::return_remaining
+/// [`drop_remaining`]: crate::iter::QueueIterReturnable::drop_remaining
+/// [`pull_returnable`]: crate::ConcurrentQueue::pull_returnable
+pub struct QueueIterReturnable<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    queue: &'a ConcurrentQueue<T, P>,
+    iter: P::PtrIter<'a>,
+}
+
+impl<'a, T, P> QueueIterReturnable<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    pub(crate) fn new(queue: &'a ConcurrentQueue<T, P>, iter: P::PtrIter<'a>) -> Self {
+        Self { queue, iter }
+    }
+
+    /// Pushes the remaining elements of the chunk back to the queue:
+    ///
+    /// * returns Ok if the queue can hold all remaining elements, which are then pushed to its back,
+    /// * returns back the chunk as the Err variant otherwise, leaving the queue untouched, so that the caller can
+    ///   recover the remaining elements by iterating over it.
+    ///
+    /// Note that dropping the returned chunk tries to return the elements once again and drops them if the queue
+    /// still cannot hold them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::with_fixed_capacity(4);
+    /// queue.extend(0..4);
+    ///
+    /// let mut chunk = queue.pull_returnable(3).unwrap();
+    /// assert_eq!(chunk.next(), Some(0));
+    ///
+    /// // the queue cannot grow beyond its 4 positions
+    /// let chunk = chunk.return_remaining().unwrap_err();
+    /// assert_eq!(chunk.collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(queue.len(), 1);
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..4);
+    ///
+    /// let mut chunk = queue.pull_returnable(3).unwrap();
+    /// assert_eq!(chunk.next(), Some(0));
+    /// assert!(chunk.return_remaining().is_ok());
+    /// assert_eq!(queue.into_inner(), vec![3, 1, 2]);
+    /// ```
+    pub fn return_remaining(mut self) -> Result<(), Self> {
+        match self.try_return_remaining() {
+            true => Ok(()),
+            false => Err(self),
+        }
+    }
+
+    /// Pushes the remaining elements back to the queue if it can hold all of them; returns whether or not
+    /// the elements are returned.
+    fn try_return_remaining(&mut self) -> bool {
+        // remaining elements are read from the pulled positions, which are never written again,
+        // and pushed to newly reserved positions at the back of the queue; they are read only if
+        // the positions are successfully reserved
+        let remaining = self.iter.by_ref().map(|ptr| unsafe { ptr.read() });
+        self.queue.try_extend(remaining).is_ok()
+    }
+
+    /// Drops the remaining elements of the chunk rather than pushing them back to the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..4);
+    ///
+    /// let mut chunk = queue.pull_returnable(3).unwrap();
+    /// assert_eq!(chunk.next(), Some(0));
+    /// chunk.drop_remaining();
+    ///
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn drop_remaining(mut self) {
+        for ptr in self.iter.by_ref() {
+            unsafe { ptr.drop_in_place() };
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for QueueIterReturnable<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|ptr| unsafe { ptr.read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, P> ExactSizeIterator for QueueIterReturnable<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, T, P> Drop for QueueIterReturnable<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    fn drop(&mut self) {
+        // panicking here would abort the process if the iterator is dropped while unwinding;
+        // hence, elements which cannot be returned are dropped as the last resort
+        if self.iter.len() > 0 && !self.try_return_remaining() {
+            for ptr in self.iter.by_ref() {
+                unsafe { ptr.drop_in_place() };
+            }
+        }
+    }
+}
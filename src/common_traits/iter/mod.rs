@@ -1,7 +1,13 @@
+mod drain;
 mod iter_of_mut;
 mod iter_of_ref;
 mod iter_owned;
+mod iter_returnable;
+mod pulled_slices;
 
+pub use drain::QueueDrain;
 pub(crate) use iter_of_mut::QueueIterOfMut;
 pub(crate) use iter_of_ref::QueueIterOfRef;
 pub use iter_owned::QueueIterOwned;
+pub use iter_returnable::QueueIterReturnable;
+pub use pulled_slices::PulledSlices;
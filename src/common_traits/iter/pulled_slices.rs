@@ -0,0 +1,172 @@
+use crate::{ConcurrentQueue, common_traits::iter::QueueIterOwned};
+use alloc::vec::Vec;
+use core::ops::Range;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// A chunk of consecutive elements pulled from the concurrent queue which provides access to the elements
+/// as contiguous slices.
+///
+/// Since the storage of the queue is a pinned vector, the pulled range is made up of a few contiguous fragments;
+/// each of which is exposed as a slice by [`slices`] and [`slices_mut`].
+/// This allows to process the batch with slice operations such as `copy_from_slice`, `sort_unstable` or SIMD
+/// without reading the elements one by one.
+///
+/// The pulled elements are owned by the chunk:
+/// * they can be moved out by [`into_iter`] or [`append_to`],
+/// * otherwise, they are dropped together with the chunk.
+///
+/// The chunk is created by [`pull_slices`].
+///
+/// [`slices`]: crate::iter::PulledSlices::slices
+/// [`slices_mut`]: crate::iter::PulledSlices::slices_mut
+/// [`into_iter`]: crate::iter::PulledSlices::into_iter
+/// [`append_to`]: crate::iter::PulledSlices::append_to
+/// [`pull_slices`]: crate::ConcurrentQueue::pull_slices
+pub struct PulledSlices<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    queue: &'a ConcurrentQueue<T, P>,
+    range: Range<usize>,
+    slices: Vec<&'a mut [T]>,
+}
+
+impl<'a, T, P> PulledSlices<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    /// # Safety
+    ///
+    /// Elements in the `range` must be written and exclusively owned by the caller.
+    pub(crate) unsafe fn new(
+        queue: &'a ConcurrentQueue<T, P>,
+        range: Range<usize>,
+        slices: Vec<&'a mut [T]>,
+    ) -> Self {
+        Self {
+            queue,
+            range,
+            slices,
+        }
+    }
+
+    /// Returns the index of the first pulled element.
+    pub fn begin_idx(&self) -> usize {
+        self.range.start
+    }
+
+    /// Returns the number of pulled elements.
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns true if the chunk has no elements, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns the pulled elements as contiguous slices, in the order of the elements in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::with_linear_growth(2, 8);
+    /// queue.extend(0..10);
+    ///
+    /// let chunk = queue.pull_slices(6).unwrap();
+    /// // fragments have a capacity of 4
+    /// let slices: Vec<_> = chunk.slices().collect();
+    /// assert_eq!(slices, [&[0, 1, 2, 3][..], &[4, 5][..]]);
+    /// ```
+    pub fn slices(&self) -> impl ExactSizeIterator<Item = &[T]> {
+        self.slices.iter().map(|x| &**x)
+    }
+
+    /// Returns the pulled elements as contiguous mutable slices, in the order of the elements in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend([3, 1, 2, 0]);
+    ///
+    /// let mut chunk = queue.pull_slices(4).unwrap();
+    /// for slice in chunk.slices_mut() {
+    ///     slice.sort_unstable();
+    /// }
+    ///
+    /// assert_eq!(chunk.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn slices_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [T]> {
+        self.slices.iter_mut().map(|x| &mut **x)
+    }
+
+    /// Moves all pulled elements to the end of the `target` vector, copying each slice at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend((0..5).map(|x| x.to_string()));
+    ///
+    /// let mut target = vec!["x".to_string()];
+    /// queue.pull_slices(3).unwrap().append_to(&mut target);
+    ///
+    /// assert_eq!(target, ["x", "0", "1", "2"]);
+    /// ```
+    pub fn append_to(mut self, target: &mut Vec<T>) {
+        target.reserve(self.len());
+        // the chunk is dropped without any slices, and hence, without dropping the moved elements
+        for slice in core::mem::take(&mut self.slices) {
+            let len = target.len();
+            // SAFETY: capacity is reserved and elements of the slice are owned by the chunk; hence, they are moved exactly once
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    slice.as_ptr(),
+                    target.as_mut_ptr().add(len),
+                    slice.len(),
+                );
+                target.set_len(len + slice.len());
+            }
+        }
+    }
+}
+
+impl<'a, T, P> IntoIterator for PulledSlices<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    type Item = T;
+
+    type IntoIter = QueueIterOwned<'a, T, P>;
+
+    /// Converts the chunk into an iterator which moves out the pulled elements one by one.
+    fn into_iter(mut self) -> Self::IntoIter {
+        // ownership of the elements is transferred to the iterator; the chunk is dropped without any slices
+        self.slices.clear();
+        let iter = unsafe { self.queue.ptr_iter_over(self.range.clone()) };
+        QueueIterOwned::new(iter)
+    }
+}
+
+impl<'a, T, P> Drop for PulledSlices<'a, T, P>
+where
+    T: Send + 'a,
+    P: ConcurrentPinnedVec<T> + 'a,
+{
+    fn drop(&mut self) {
+        for slice in self.slices.iter_mut() {
+            // SAFETY: elements are owned by the chunk
+            unsafe { core::ptr::drop_in_place(*slice as *mut [T]) };
+        }
+    }
+}
@@ -1,6 +1,14 @@
 #[cfg(test)]
 mod tests;
 
+mod clone;
+#[cfg(feature = "concurrent-bag")]
+mod concurrent_bag;
+#[cfg(feature = "concurrent-vec")]
+mod concurrent_vec;
+mod debug;
+mod eq;
+mod from_iter;
 mod into_iter;
 /// Module containing iterator implementations over the concurrent queue.
 pub mod iter;
@@ -0,0 +1,67 @@
+use crate::ConcurrentQueue;
+use alloc::vec::Vec;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::{SplitVec, prelude::PseudoDefault};
+use std::string::{String, ToString};
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 37;
+#[cfg(not(miri))]
+const N: usize = 1735;
+
+#[test_matrix(
+    [FixedVec::new(N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, N / 16 + 1)],
+    [0, N / 3, N]
+)]
+fn clone_pending_elements<P>(vec: P, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<String> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<String, P = P>,
+{
+    let queue = ConcurrentQueue::from(vec);
+    queue.extend((0..N).map(|x| x.to_string()));
+    _ = queue.pull(num_popped);
+
+    let clone = queue.clone();
+    assert_eq!(clone, queue);
+    assert_eq!(clone.max_capacity(), queue.max_capacity());
+    assert_eq!(clone.chunk_policy(), queue.chunk_policy());
+
+    let expected: Vec<_> = (num_popped..N).map(|x| x.to_string()).collect();
+    let mut clone = clone;
+    assert_eq!(clone.iter().cloned().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn clone_while_popping() {
+    let queue = ConcurrentQueue::new();
+    queue.extend((0..N).map(|x| x.to_string()));
+    let queue = &queue;
+
+    let num_popped = std::thread::scope(|s| {
+        let poppers: Vec<_> = (0..2)
+            .map(|_| s.spawn(move || core::iter::from_fn(|| queue.pop()).count()))
+            .collect();
+
+        for _ in 0..4 {
+            let clone = queue.clone();
+            let mut elements = clone.into_iter();
+            // pending elements form a suffix of the pushed elements
+            if let Some(first) = elements.next() {
+                let first: usize = first.parse().expect("pushed elements are numbers");
+                let rest: Vec<_> = elements.collect();
+                let expected: Vec<_> = ((first + 1)..N).map(|x| x.to_string()).collect();
+                assert_eq!(rest, expected);
+            }
+        }
+
+        poppers
+            .into_iter()
+            .map(|x| x.join().expect("poppers do not panic"))
+            .sum::<usize>()
+    });
+
+    assert_eq!(num_popped, N);
+}
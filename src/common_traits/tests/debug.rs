@@ -0,0 +1,23 @@
+use crate::ConcurrentQueue;
+use alloc::format;
+use orx_split_vec::SplitVec;
+
+#[test]
+fn debug_shows_counters_and_pending_elements() {
+    let queue = ConcurrentQueue::new();
+    queue.extend(0..5);
+    _ = queue.pull(2);
+
+    let expected =
+        "ConcurrentQueue { written: 5, write_reserved: 5, popped: 2, elements: [2, 3, 4] }";
+    assert_eq!(format!("{queue:?}"), expected);
+}
+
+#[test]
+fn debug_empty() {
+    let queue: ConcurrentQueue<char, _> =
+        ConcurrentQueue::from(SplitVec::with_linear_growth_and_fragments_capacity(4, 8));
+
+    let expected = "ConcurrentQueue { written: 0, write_reserved: 0, popped: 0, elements: [] }";
+    assert_eq!(format!("{queue:?}"), expected);
+}
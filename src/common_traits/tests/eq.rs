@@ -0,0 +1,27 @@
+use crate::ConcurrentQueue;
+use orx_fixed_vec::FixedVec;
+use orx_split_vec::SplitVec;
+
+#[test]
+fn eq_compares_pending_elements() {
+    let a = ConcurrentQueue::new();
+    a.extend(0..10);
+    _ = a.pull(4);
+
+    let b = ConcurrentQueue::from(FixedVec::new(10));
+    b.extend(4..10);
+    assert_eq!(a, b);
+
+    let c = ConcurrentQueue::from(SplitVec::with_linear_growth(2));
+    c.extend(4..11);
+    assert_ne!(a, c);
+    _ = c.pull(1);
+    assert_ne!(a, c);
+
+    let d: ConcurrentQueue<i32> = ConcurrentQueue::new();
+    let e = ConcurrentQueue::from(FixedVec::new(3));
+    e.extend([1, 2, 3]);
+    _ = e.pull(3);
+    assert_eq!(d, e);
+    assert_eq!(a, a);
+}
@@ -0,0 +1,23 @@
+use crate::ConcurrentQueue;
+use alloc::vec::Vec;
+use orx_fixed_vec::FixedVec;
+
+#[test]
+fn collect_into_queue() {
+    let queue: ConcurrentQueue<_> = (0..100).filter(|x| x % 3 == 0).collect();
+    assert_eq!(queue.len(), 34);
+    assert_eq!(
+        queue.into_inner(),
+        (0..100).filter(|x| x % 3 == 0).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn extend_from_iter() {
+    let mut queue = ConcurrentQueue::from(FixedVec::new(10));
+    queue.push(0);
+    Extend::extend(&mut queue, (1..6).filter(|x| x % 2 == 1));
+    Extend::extend(&mut queue, &[7, 8]);
+    _ = queue.pop();
+    assert_eq!(queue.into_inner(), [1, 3, 5, 7, 8]);
+}
@@ -1 +1,5 @@
+mod clone;
+mod debug;
+mod eq;
+mod from_iter;
 mod into_iter;
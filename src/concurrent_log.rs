@@ -0,0 +1,286 @@
+use crate::{ConcurrentQueue, DefaultConPinnedVec};
+use core::ops::Range;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::prelude::PseudoDefault;
+
+/// A thread safe append-only log where every appended entry is assigned a `u64` sequence number,
+/// its position in the log.
+///
+/// * Entries are appended with a shared reference by [`append`] or [`extend`], which return the sequence numbers of the new entries.
+/// * Any committed entry can be read concurrently by its sequence number with [`get`].
+/// * Entries can be replayed starting from any sequence number with [`iter_from`].
+///
+/// An entry is committed once it is completely written and all entries before it are committed.
+/// Therefore, committed entries always form the prefix `0..len` of the log.
+///
+/// The log uses the same concurrent pinned vector storage as the [`ConcurrentQueue`].
+/// Since entries are never moved or removed, references to the entries remain valid while the log grows.
+///
+/// [`append`]: crate::ConcurrentLog::append
+/// [`extend`]: crate::ConcurrentLog::extend
+/// [`get`]: crate::ConcurrentLog::get
+/// [`iter_from`]: crate::ConcurrentLog::iter_from
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::ConcurrentLog;
+///
+/// let log = ConcurrentLog::new();
+///
+/// assert_eq!(log.append('a'), 0);
+/// assert_eq!(log.append('b'), 1);
+/// assert_eq!(log.extend(['c', 'd']), 2..4);
+///
+/// assert_eq!(log.get(1), Some(&'b'));
+/// assert_eq!(log.get(4), None);
+///
+/// let replay: Vec<_> = log.iter_from(2).collect();
+/// assert_eq!(replay, vec![&'c', &'d']);
+/// ```
+///
+/// The following example demonstrates concurrent writers and readers of the log.
+///
+/// ```
+/// use orx_concurrent_queue::ConcurrentLog;
+///
+/// let log = ConcurrentLog::new();
+///
+/// std::thread::scope(|s| {
+///     for t in 0..4 {
+///         let log = &log;
+///         s.spawn(move || {
+///             for i in 0..100 {
+///                 let seq = log.append(t * 100 + i);
+///                 assert_eq!(log.get(seq), Some(&(t * 100 + i)));
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(log.len(), 400);
+/// assert_eq!(log.iter().sum::<usize>(), 399 * 400 / 2);
+/// ```
+pub struct ConcurrentLog<T, P = DefaultConPinnedVec<T>>
+where
+    T: Send + Sync,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: ConcurrentQueue<T, P>,
+}
+
+impl<T, P> From<P> for ConcurrentLog<T, P::ConPinnedVec>
+where
+    T: Send + Sync,
+    P: IntoConcurrentPinnedVec<T>,
+{
+    /// Creates a log from the given pinned vector where the elements of the vector become the first entries of the log.
+    fn from(vec: P) -> Self {
+        Self { queue: vec.into() }
+    }
+}
+
+impl<T> Default for ConcurrentLog<T, DefaultConPinnedVec<T>>
+where
+    T: Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ConcurrentLog<T, DefaultConPinnedVec<T>>
+where
+    T: Send + Sync,
+{
+    /// Creates a new empty concurrent log.
+    ///
+    /// This log is backed with default concurrent pinned vec, which is the concurrent version of [`SplitVec`] with [`Doubling`] growth.
+    ///
+    /// In order to create a log backed with a particular [`PinnedVec`], you may use the `From` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    /// use orx_fixed_vec::FixedVec;
+    ///
+    /// let log: ConcurrentLog<u64> = ConcurrentLog::new();
+    ///
+    /// // in order to create a log from a different pinned vec, use into, rather than new:
+    /// let log: ConcurrentLog<u64, _> = FixedVec::new(1000).into();
+    /// ```
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Doubling`]: orx_split_vec::Doubling
+    /// [`PinnedVec`]: orx_pinned_vec::PinnedVec
+    pub fn new() -> Self {
+        Self {
+            queue: ConcurrentQueue::new(),
+        }
+    }
+}
+
+impl<T, P> ConcurrentLog<T, P>
+where
+    T: Send + Sync,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Converts the log into the underlying pinned vector containing all entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    ///
+    /// let log = ConcurrentLog::new();
+    /// log.extend([1, 2, 3]);
+    ///
+    /// assert_eq!(log.into_inner(), vec![1, 2, 3]);
+    /// ```
+    pub fn into_inner(self) -> <P as ConcurrentPinnedVec<T>>::P
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+    {
+        self.queue.into_inner()
+    }
+
+    // grow
+
+    /// Appends the `value` to the end of the log and returns its sequence number.
+    ///
+    /// The entry is committed, and hence, visible to [`get`] and [`iter_from`], once this method returns.
+    ///
+    /// [`get`]: crate::ConcurrentLog::get
+    /// [`iter_from`]: crate::ConcurrentLog::iter_from
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    ///
+    /// let log = ConcurrentLog::new();
+    ///
+    /// let seq = log.append("created");
+    /// assert_eq!(seq, 0);
+    /// assert_eq!(log.get(seq), Some(&"created"));
+    /// ```
+    pub fn append(&self, value: T) -> u64 {
+        seq_of(self.queue.push_returning_idx(value))
+    }
+
+    /// Appends all `values` to the end of the log as consecutive entries and returns the range of their sequence numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    ///
+    /// let log = ConcurrentLog::new();
+    ///
+    /// assert_eq!(log.extend(0..3), 0..3);
+    /// assert_eq!(log.extend(vec![3, 4]), 3..5);
+    /// assert_eq!(log.len(), 5);
+    /// ```
+    pub fn extend<I, Iter>(&self, values: I) -> Range<u64>
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        let range = self.queue.extend_returning_range(values);
+        seq_of(range.start)..seq_of(range.end)
+    }
+
+    // get
+
+    /// Returns a reference to the entry with the given sequence number `seq`;
+    /// returns None if the entry is not committed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    ///
+    /// let log = ConcurrentLog::new();
+    /// log.extend(['a', 'b']);
+    ///
+    /// assert_eq!(log.get(0), Some(&'a'));
+    /// assert_eq!(log.get(1), Some(&'b'));
+    /// assert_eq!(log.get(2), None);
+    /// ```
+    pub fn get(&self, seq: u64) -> Option<&T> {
+        match usize::try_from(seq) {
+            Ok(idx) if idx < self.queue.num_written() => {
+                // SAFETY: committed entries are never moved, mutated or dropped while the log is alive
+                Some(unsafe { &*self.queue.ptr(idx) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator replaying the entries of the log starting from the sequence number `seq`.
+    ///
+    /// The iterator is lazy: it yields the entries committed by the time it reaches them,
+    /// and it ends once it reaches the first entry which is not committed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    ///
+    /// let log = ConcurrentLog::new();
+    /// log.extend(0..4);
+    ///
+    /// let mut replay = log.iter_from(2);
+    /// assert_eq!(replay.next(), Some(&2));
+    ///
+    /// log.append(4);
+    /// assert_eq!(replay.collect::<Vec<_>>(), vec![&3, &4]);
+    ///
+    /// assert_eq!(log.iter_from(10).next(), None);
+    /// ```
+    pub fn iter_from(&self, seq: u64) -> impl Iterator<Item = &T> {
+        (seq..=u64::MAX).map_while(|i| self.get(i))
+    }
+
+    /// Returns an iterator replaying all entries of the log, equivalent to `iter_from(0)`.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.iter_from(0)
+    }
+
+    /// Returns the number of committed entries of the log.
+    pub fn len(&self) -> usize {
+        self.queue.num_written()
+    }
+
+    /// Returns the sequence number of the next committed entry, which is also the number of committed entries of the log.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentLog;
+    ///
+    /// let log = ConcurrentLog::new();
+    /// assert_eq!(log.next_seq(), 0);
+    ///
+    /// log.extend(['a', 'b', 'c']);
+    /// assert_eq!(log.next_seq(), 3);
+    /// ```
+    pub fn next_seq(&self) -> u64 {
+        seq_of(self.queue.num_written())
+    }
+
+    /// Returns true if the log has no committed entries, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns the sequence number of the entry at position `idx` of the underlying storage.
+#[inline(always)]
+fn seq_of(idx: usize) -> u64 {
+    // usize is at most 64 bits wide on all supported targets
+    idx as u64
+}
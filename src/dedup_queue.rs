@@ -0,0 +1,293 @@
+use crate::{ConcurrentQueue, DefaultConPinnedVec, lock_utils::lock_ignoring_poison};
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use std::{
+    collections::HashSet,
+    hash::RandomState,
+    sync::{Mutex, MutexGuard},
+};
+
+/// Number of independently locked shards of the set of known elements.
+const NUM_SHARDS: usize = 32;
+
+/// Determines which elements are rejected by the [`DedupConcurrentQueue`].
+///
+/// [`DedupConcurrentQueue`]: crate::DedupConcurrentQueue
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DedupPolicy {
+    /// An element is rejected if an equal element is currently pending in the queue.
+    ///
+    /// Once the element is popped, an equal element can be pushed again.
+    #[default]
+    Pending,
+    /// An element is rejected if an equal element has ever been pushed to the queue.
+    EverSeen,
+}
+
+/// A thread safe queue which rejects elements equal to an element already pending in the queue,
+/// or optionally, equal to any element ever pushed to the queue.
+///
+/// * [`push`] returns false and drops the value if it is rejected.
+/// * [`pop`] releases the popped element, so that an equal element can be pushed again,
+///   unless the policy is [`DedupPolicy::EverSeen`].
+///
+/// Elements are stored in a [`ConcurrentQueue`] while the known elements are tracked in a set
+/// which is sharded by hash in order to reduce contention.
+/// Since both the queue and the set are updated by the same calls, deduplication is consistent with the pops:
+/// an element is considered pending until the `pop` call which returns it completes.
+///
+/// The set keeps a clone of each known element; therefore, cheaply cloneable elements such as ids or `Arc`s are preferable.
+///
+/// [`push`]: crate::DedupConcurrentQueue::push
+/// [`pop`]: crate::DedupConcurrentQueue::pop
+/// [`DedupPolicy::EverSeen`]: crate::DedupPolicy::EverSeen
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::DedupConcurrentQueue;
+///
+/// let queue = DedupConcurrentQueue::new();
+///
+/// assert!(queue.push('a'));
+/// assert!(queue.push('b'));
+/// assert!(!queue.push('a')); // 'a' is pending
+///
+/// assert_eq!(queue.pop(), Some('a'));
+/// assert!(queue.push('a')); // 'a' is not pending anymore
+///
+/// assert_eq!(queue.pop(), Some('b'));
+/// assert_eq!(queue.pop(), Some('a'));
+/// assert_eq!(queue.pop(), None);
+/// ```
+///
+/// The following example demonstrates multiple threads discovering overlapping sets of nodes,
+/// where each node is enqueued only once.
+///
+/// ```
+/// use orx_concurrent_queue::DedupConcurrentQueue;
+///
+/// let queue = DedupConcurrentQueue::new();
+///
+/// std::thread::scope(|s| {
+///     for t in 0..4 {
+///         let queue = &queue;
+///         s.spawn(move || {
+///             for node in (t * 50)..(t * 50 + 100) {
+///                 queue.push(node);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(queue.len(), 250);
+///
+/// let mut nodes: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+/// nodes.sort();
+/// assert_eq!(nodes, (0..250).collect::<Vec<_>>());
+/// ```
+pub struct DedupConcurrentQueue<T, P = DefaultConPinnedVec<T>>
+where
+    T: Send + Hash + Eq + Clone,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: ConcurrentQueue<T, P>,
+    shards: Vec<Mutex<HashSet<T>>>,
+    hasher: RandomState,
+    policy: DedupPolicy,
+}
+
+impl<T, P> From<P> for DedupConcurrentQueue<T, P::ConPinnedVec>
+where
+    T: Send + Hash + Eq + Clone,
+    P: IntoConcurrentPinnedVec<T>,
+{
+    /// Creates a queue with the [`DedupPolicy::Pending`] policy from the given pinned vector which must be empty.
+    ///
+    /// [`DedupPolicy::Pending`]: crate::DedupPolicy::Pending
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is not empty, since its elements are not known to be distinct.
+    fn from(vec: P) -> Self {
+        Self::from_queue(vec.into(), DedupPolicy::Pending)
+    }
+}
+
+impl<T> Default for DedupConcurrentQueue<T, DefaultConPinnedVec<T>>
+where
+    T: Send + Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DedupConcurrentQueue<T, DefaultConPinnedVec<T>>
+where
+    T: Send + Hash + Eq + Clone,
+{
+    /// Creates a new empty queue which rejects elements equal to a pending element, the [`DedupPolicy::Pending`] policy.
+    ///
+    /// [`DedupPolicy::Pending`]: crate::DedupPolicy::Pending
+    pub fn new() -> Self {
+        Self::with_policy(DedupPolicy::Pending)
+    }
+
+    /// Creates a new empty queue with the given deduplication `policy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{DedupConcurrentQueue, DedupPolicy};
+    ///
+    /// let queue = DedupConcurrentQueue::with_policy(DedupPolicy::EverSeen);
+    ///
+    /// assert!(queue.push(42));
+    /// assert_eq!(queue.pop(), Some(42));
+    /// assert!(!queue.push(42)); // 42 has already been seen
+    /// ```
+    pub fn with_policy(policy: DedupPolicy) -> Self {
+        Self::from_queue(ConcurrentQueue::new(), policy)
+    }
+}
+
+impl<T, P> DedupConcurrentQueue<T, P>
+where
+    T: Send + Hash + Eq + Clone,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Creates a deduplicating queue with the given `policy` using the given empty `queue` as the storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `queue` is not empty, since its elements are not known to be distinct.
+    pub fn from_queue(queue: ConcurrentQueue<T, P>, policy: DedupPolicy) -> Self {
+        assert!(
+            queue.is_empty(),
+            "Deduplicating queue must be created from an empty queue."
+        );
+        Self {
+            queue,
+            shards: (0..NUM_SHARDS)
+                .map(|_| Mutex::new(HashSet::new()))
+                .collect(),
+            hasher: RandomState::new(),
+            policy,
+        }
+    }
+
+    /// Returns the deduplication policy of the queue.
+    pub fn policy(&self) -> DedupPolicy {
+        self.policy
+    }
+
+    // shrink
+
+    /// Pops and returns the element in the front of the queue; returns None if the queue is empty.
+    ///
+    /// With the [`DedupPolicy::Pending`] policy, an element equal to the popped element can be pushed again once this method returns.
+    ///
+    /// [`DedupPolicy::Pending`]: crate::DedupPolicy::Pending
+    pub fn pop(&self) -> Option<T> {
+        let value = self.queue.pop()?;
+        if self.policy == DedupPolicy::Pending {
+            _ = self.lock_shard_of(&value).remove(&value);
+        }
+        Some(value)
+    }
+
+    // grow
+
+    /// Pushes the `value` to the back of the queue and returns true if it is accepted;
+    /// returns false and drops the `value` if it is rejected due to the deduplication policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::DedupConcurrentQueue;
+    ///
+    /// let queue = DedupConcurrentQueue::new();
+    ///
+    /// assert!(queue.push("x".to_string()));
+    /// assert!(!queue.push("x".to_string()));
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn push(&self, value: T) -> bool {
+        let accepted = self.lock_shard_of(&value).insert(value.clone());
+        if accepted {
+            // the value cannot be popped before it is pushed; hence, it is safe to push after releasing the shard
+            self.queue.push(value);
+        }
+        accepted
+    }
+
+    /// Pushes each of the `values` which is accepted by the deduplication policy to the back of the queue;
+    /// returns the number of accepted values.
+    ///
+    /// Accepted values are pushed as consecutive elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::DedupConcurrentQueue;
+    ///
+    /// let queue = DedupConcurrentQueue::new();
+    ///
+    /// assert_eq!(queue.extend([1, 2, 1, 3, 2]), 3);
+    /// assert_eq!(queue.extend([3, 4]), 1);
+    /// assert_eq!(queue.len(), 4);
+    /// ```
+    pub fn extend(&self, values: impl IntoIterator<Item = T>) -> usize {
+        let accepted: Vec<_> = values
+            .into_iter()
+            .filter(|x| self.lock_shard_of(x).insert(x.clone()))
+            .collect();
+        let num_accepted = accepted.len();
+        self.queue.extend(accepted);
+        num_accepted
+    }
+
+    // get
+
+    /// Returns true if an element equal to the `value` is known by the queue, in which case pushing it would be rejected;
+    /// returns false otherwise.
+    ///
+    /// With the [`DedupPolicy::Pending`] policy, known elements are the pending elements;
+    /// with the [`DedupPolicy::EverSeen`] policy, known elements are all elements ever pushed.
+    ///
+    /// [`DedupPolicy::Pending`]: crate::DedupPolicy::Pending
+    /// [`DedupPolicy::EverSeen`]: crate::DedupPolicy::EverSeen
+    pub fn contains(&self, value: &T) -> bool {
+        self.lock_shard_of(value).contains(value)
+    }
+
+    /// Returns the number of known elements; see [`contains`].
+    ///
+    /// [`contains`]: crate::DedupConcurrentQueue::contains
+    pub fn num_known(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|x| lock_ignoring_poison(x).len())
+            .sum()
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if the queue is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    // helpers
+
+    fn lock_shard_of(&self, value: &T) -> MutexGuard<'_, HashSet<T>> {
+        let shard = (self.hasher.hash_one(value) % NUM_SHARDS as u64) as usize;
+        lock_ignoring_poison(&self.shards[shard])
+    }
+}
@@ -0,0 +1,79 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// A source of the current instant used by the [`DelayQueue`] to decide whether or not an element is due.
+///
+/// [`SystemClock`] is the default clock which reads the monotonic system clock.
+/// Any other clock, such as the [`ManualClock`], can be plugged in to make the delays deterministic, which is particularly useful in tests.
+///
+/// [`DelayQueue`]: crate::DelayQueue
+pub trait Clock: Send + Sync {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+}
+
+/// The default clock of the [`DelayQueue`] which returns [`Instant::now`].
+///
+/// [`DelayQueue`]: crate::DelayQueue
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline(always)]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock which only moves forward when it is explicitly [`advance`]d.
+///
+/// It allows to test time dependent behavior of the [`DelayQueue`] deterministically.
+///
+/// [`advance`]: crate::ManualClock::advance
+/// [`DelayQueue`]: crate::DelayQueue
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::{Clock, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+///
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(clock.now() - start, Duration::from_secs(3));
+/// ```
+#[derive(Debug)]
+pub struct ManualClock {
+    start: Instant,
+    elapsed_nanos: AtomicU64,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    /// Creates a new manual clock which stands still at the current instant of the system clock.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed_nanos: 0.into(),
+        }
+    }
+
+    /// Moves the clock forward by the given `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.elapsed_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + Duration::from_nanos(self.elapsed_nanos.load(Ordering::Relaxed))
+    }
+}
@@ -0,0 +1,32 @@
+use core::cmp::Ordering;
+use std::time::Instant;
+
+/// An element of the delay queue together with its deadline.
+///
+/// Entries are ordered so that the entry with the earliest deadline is the greatest;
+/// ties are broken by the push order so that elements with equal deadlines are popped in FIFO order.
+pub struct Entry<T> {
+    pub deadline: Instant,
+    pub seq: u64,
+    pub value: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.deadline, other.seq).cmp(&(self.deadline, self.seq))
+    }
+}
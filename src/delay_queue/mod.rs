@@ -0,0 +1,6 @@
+mod clock;
+mod entry;
+mod queue;
+
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use queue::DelayQueue;
@@ -0,0 +1,341 @@
+use crate::{
+    delay_queue::{
+        clock::{Clock, SystemClock},
+        entry::Entry,
+    },
+    lock_utils::lock_ignoring_poison,
+};
+use alloc::collections::BinaryHeap;
+use std::{
+    sync::{Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// A thread safe queue where every element is pushed together with a deadline and becomes poppable only after its deadline has passed.
+///
+/// * Elements can be pushed with an absolute deadline by [`push_at`] or with a delay by [`push_after`].
+/// * [`pop`] returns the element with the earliest deadline only if its deadline has passed; otherwise, it immediately returns None.
+/// * [`pop_wait`] blocks the calling thread until the earliest deadline passes.
+/// * Elements with equal deadlines are popped in the order they are pushed.
+///
+/// The delay queue is useful for retries with backoff, timers or rate limited work,
+/// where re-pushing elements to a [`ConcurrentQueue`] and checking timestamps in the consumers would waste cycles and reorder work.
+///
+/// Time is read from the clock of the queue which is [`SystemClock`] by default.
+/// A [`ManualClock`] can be used instead to make the delays deterministic.
+///
+/// The delay queue requires the opt-in `std` feature.
+///
+/// [`push_at`]: crate::DelayQueue::push_at
+/// [`push_after`]: crate::DelayQueue::push_after
+/// [`pop`]: crate::DelayQueue::pop
+/// [`pop_wait`]: crate::DelayQueue::pop_wait
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+/// [`SystemClock`]: crate::SystemClock
+/// [`ManualClock`]: crate::ManualClock
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::{DelayQueue, ManualClock};
+/// use std::time::Duration;
+///
+/// let queue = DelayQueue::with_clock(ManualClock::new());
+///
+/// queue.push_after(Duration::from_secs(10), 'a');
+/// queue.push_after(Duration::from_secs(5), 'b');
+/// queue.push_after(Duration::from_secs(5), 'c');
+///
+/// assert_eq!(queue.len(), 3);
+/// assert_eq!(queue.pop(), None); // nothing is due yet
+///
+/// queue.clock().advance(Duration::from_secs(6));
+/// assert_eq!(queue.pop(), Some('b'));
+/// assert_eq!(queue.pop(), Some('c'));
+/// assert_eq!(queue.pop(), None);
+///
+/// queue.clock().advance(Duration::from_secs(4));
+/// assert_eq!(queue.pop(), Some('a'));
+/// assert!(queue.is_empty());
+/// ```
+///
+/// The following example demonstrates retries with backoff, where the consumers block until the next task is due.
+///
+/// ```
+/// use orx_concurrent_queue::DelayQueue;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::time::Duration;
+///
+/// struct Task {
+///     attempt: u32,
+/// }
+///
+/// let queue = DelayQueue::new();
+/// for _ in 0..4 {
+///     queue.push(Task { attempt: 0 });
+/// }
+///
+/// let num_completed = AtomicUsize::new(0);
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..2 {
+///         s.spawn(|| {
+///             while let Some(task) = queue.pop_wait() {
+///                 match task.attempt < 2 {
+///                     // fails, retry with exponential backoff
+///                     true => {
+///                         let backoff = Duration::from_millis(1 << task.attempt);
+///                         queue.push_after(backoff, Task { attempt: task.attempt + 1 });
+///                     }
+///                     // succeeds
+///                     false => _ = num_completed.fetch_add(1, Ordering::Relaxed),
+///                 }
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(num_completed.into_inner(), 4);
+/// ```
+pub struct DelayQueue<T, C = SystemClock>
+where
+    T: Send,
+    C: Clock,
+{
+    state: Mutex<State<T>>,
+    earliest_changed: Condvar,
+    clock: C,
+}
+
+struct State<T> {
+    heap: BinaryHeap<Entry<T>>,
+    next_seq: u64,
+}
+
+impl<T> Default for DelayQueue<T, SystemClock>
+where
+    T: Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DelayQueue<T, SystemClock>
+where
+    T: Send,
+{
+    /// Creates a new empty delay queue using the [`SystemClock`].
+    ///
+    /// [`SystemClock`]: crate::SystemClock
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<T, C> DelayQueue<T, C>
+where
+    T: Send,
+    C: Clock,
+{
+    /// Creates a new empty delay queue which reads the current instant from the given `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{DelayQueue, ManualClock};
+    ///
+    /// let queue: DelayQueue<String, _> = DelayQueue::with_clock(ManualClock::new());
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn with_clock(clock: C) -> Self {
+        Self {
+            state: Mutex::new(State {
+                heap: BinaryHeap::new(),
+                next_seq: 0,
+            }),
+            earliest_changed: Condvar::new(),
+            clock,
+        }
+    }
+
+    /// Returns a reference to the clock of the queue.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    // grow
+
+    /// Pushes the `value` to the queue which becomes poppable once the `deadline` has passed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{Clock, DelayQueue, ManualClock};
+    /// use std::time::Duration;
+    ///
+    /// let queue = DelayQueue::with_clock(ManualClock::new());
+    ///
+    /// let deadline = queue.clock().now() + Duration::from_millis(100);
+    /// queue.push_at(deadline, 42);
+    /// assert_eq!(queue.pop(), None);
+    ///
+    /// queue.clock().advance(Duration::from_millis(100));
+    /// assert_eq!(queue.pop(), Some(42));
+    /// ```
+    pub fn push_at(&self, deadline: Instant, value: T) {
+        let mut state = self.lock();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+
+        let is_earliest = state.heap.peek().is_none_or(|x| deadline < x.deadline);
+        state.heap.push(Entry {
+            deadline,
+            seq,
+            value,
+        });
+        drop(state);
+
+        if is_earliest {
+            self.earliest_changed.notify_one();
+        }
+    }
+
+    /// Pushes the `value` to the queue which becomes poppable once the `delay` elapses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{DelayQueue, ManualClock};
+    /// use std::time::Duration;
+    ///
+    /// let queue = DelayQueue::with_clock(ManualClock::new());
+    ///
+    /// queue.push_after(Duration::from_secs(1), 42);
+    /// assert_eq!(queue.pop(), None);
+    ///
+    /// queue.clock().advance(Duration::from_secs(1));
+    /// assert_eq!(queue.pop(), Some(42));
+    /// ```
+    pub fn push_after(&self, delay: Duration, value: T) {
+        self.push_at(self.clock.now() + delay, value)
+    }
+
+    /// Pushes the `value` to the queue which is immediately poppable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::DelayQueue;
+    ///
+    /// let queue = DelayQueue::new();
+    ///
+    /// queue.push(42);
+    /// assert_eq!(queue.pop(), Some(42));
+    /// ```
+    pub fn push(&self, value: T) {
+        self.push_at(self.clock.now(), value)
+    }
+
+    // shrink
+
+    /// Pops and returns the element with the earliest deadline if its deadline has passed;
+    /// returns None if the queue is empty or if none of the elements is due yet.
+    ///
+    /// This method never blocks on waiting for a deadline; see [`pop_wait`] for the blocking variant.
+    ///
+    /// [`pop_wait`]: crate::DelayQueue::pop_wait
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self.lock();
+        let now = self.clock.now();
+        match state.heap.peek() {
+            Some(x) if x.deadline <= now => state.heap.pop().map(|x| x.value),
+            _ => None,
+        }
+    }
+
+    /// Pops and returns the element with the earliest deadline, blocking the calling thread until its deadline passes;
+    /// returns None if the queue is empty.
+    ///
+    /// While waiting, the thread wakes up if an element with an earlier deadline is pushed.
+    /// If other threads pop all elements in the meantime, the method returns None.
+    ///
+    /// Note that waiting is performed in real time.
+    /// When the queue uses a [`ManualClock`], the waiting thread re-checks the deadlines at the latest after
+    /// the remaining time to the earliest deadline passes in real time.
+    ///
+    /// [`ManualClock`]: crate::ManualClock
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::DelayQueue;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let queue = DelayQueue::new();
+    ///
+    /// let begin = Instant::now();
+    /// queue.push_after(Duration::from_millis(20), 42);
+    ///
+    /// assert_eq!(queue.pop_wait(), Some(42));
+    /// assert!(begin.elapsed() >= Duration::from_millis(20));
+    ///
+    /// assert_eq!(queue.pop_wait(), None);
+    /// ```
+    pub fn pop_wait(&self) -> Option<T> {
+        let mut state = self.lock();
+        loop {
+            let now = self.clock.now();
+            let deadline = state.heap.peek()?.deadline;
+            match deadline <= now {
+                true => return state.heap.pop().map(|x| x.value),
+                false => {
+                    state = self
+                        .earliest_changed
+                        .wait_timeout(state, deadline - now)
+                        .map(|(state, _)| state)
+                        .unwrap_or_else(|e| e.into_inner().0);
+                }
+            }
+        }
+    }
+
+    // get
+
+    /// Returns the number of elements in the queue, including the ones which are not due yet.
+    pub fn len(&self) -> usize {
+        self.lock().heap.len()
+    }
+
+    /// Returns true if the queue is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.lock().heap.is_empty()
+    }
+
+    /// Returns the earliest deadline among the elements of the queue; returns None if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{Clock, DelayQueue, ManualClock};
+    /// use std::time::Duration;
+    ///
+    /// let queue = DelayQueue::with_clock(ManualClock::new());
+    /// assert_eq!(queue.next_deadline(), None);
+    ///
+    /// let now = queue.clock().now();
+    /// queue.push_after(Duration::from_secs(2), 'a');
+    /// queue.push_after(Duration::from_secs(1), 'b');
+    ///
+    /// assert_eq!(queue.next_deadline(), Some(now + Duration::from_secs(1)));
+    /// ```
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.lock().heap.peek().map(|x| x.deadline)
+    }
+
+    // helpers
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        lock_ignoring_poison(&self.state)
+    }
+}
@@ -0,0 +1,12 @@
+/// An element of the leased queue together with the number of times it has been delivered so far.
+pub struct Delivery<T> {
+    pub value: T,
+    pub num_deliveries: usize,
+}
+
+/// A clone of a leased element kept by the queue in order to redeliver it if the lease expires.
+#[cfg(feature = "std")]
+pub struct Expiring<T> {
+    pub value: T,
+    pub num_deliveries: usize,
+}
@@ -0,0 +1,125 @@
+use crate::LeasedQueue;
+#[cfg(feature = "std")]
+use crate::leased::queue::LeaseKey;
+use core::{
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+/// An element popped from a [`LeasedQueue`] which is not yet acknowledged.
+///
+/// * [`ack`] finalizes the delivery and returns the element.
+/// * [`nack`] or dropping the lease puts the element back to the queue for redelivery,
+///   or to the dead letters queue if the maximum number of deliveries is reached.
+///
+/// The lease dereferences to the leased element.
+///
+/// [`LeasedQueue`]: crate::LeasedQueue
+/// [`ack`]: crate::Lease::ack
+/// [`nack`]: crate::Lease::nack
+pub struct Lease<'a, T>
+where
+    T: Send,
+{
+    queue: &'a LeasedQueue<T>,
+    value: ManuallyDrop<T>,
+    num_deliveries: usize,
+    #[cfg(feature = "std")]
+    key: Option<LeaseKey>,
+}
+
+impl<'a, T> Lease<'a, T>
+where
+    T: Send,
+{
+    pub(super) fn new(queue: &'a LeasedQueue<T>, value: T, num_deliveries: usize) -> Self {
+        Self {
+            queue,
+            value: ManuallyDrop::new(value),
+            num_deliveries,
+            #[cfg(feature = "std")]
+            key: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(super) fn with_timeout(
+        queue: &'a LeasedQueue<T>,
+        value: T,
+        num_deliveries: usize,
+        key: LeaseKey,
+    ) -> Self {
+        Self {
+            queue,
+            value: ManuallyDrop::new(value),
+            num_deliveries,
+            key: Some(key),
+        }
+    }
+
+    /// Returns the number of times the leased element has been delivered, including this delivery.
+    ///
+    /// Therefore, the number of deliveries is 1 for the first delivery of an element.
+    pub fn num_deliveries(&self) -> usize {
+        self.num_deliveries
+    }
+
+    /// Acknowledges that the leased element is successfully processed and returns the element.
+    ///
+    /// The element will not be redelivered, unless the lease has already expired.
+    pub fn ack(self) -> T {
+        let mut lease = ManuallyDrop::new(self);
+        #[cfg(feature = "std")]
+        if let Some(key) = lease.key.take() {
+            _ = lease.queue.release_expiring(&key);
+        }
+        // SAFETY: the lease is never dropped; hence, value is taken out only once
+        unsafe { ManuallyDrop::take(&mut lease.value) }
+    }
+
+    /// Rejects the leased element, which puts it back to the queue for redelivery,
+    /// or to the dead letters queue if the maximum number of deliveries is reached.
+    ///
+    /// This is equivalent to dropping the lease.
+    pub fn nack(self) {}
+}
+
+impl<T> Drop for Lease<'_, T>
+where
+    T: Send,
+{
+    fn drop(&mut self) {
+        // SAFETY: value is taken out only here and in ack, which never drops the lease
+        let value = unsafe { ManuallyDrop::take(&mut self.value) };
+
+        #[cfg(feature = "std")]
+        if let Some(key) = self.key.take()
+            && !self.queue.release_expiring(&key)
+        {
+            // expired lease is already redelivered
+            return;
+        }
+
+        self.queue.redeliver(value, self.num_deliveries);
+    }
+}
+
+impl<T> Deref for Lease<'_, T>
+where
+    T: Send,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Lease<'_, T>
+where
+    T: Send,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
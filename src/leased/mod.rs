@@ -0,0 +1,6 @@
+mod delivery;
+mod lease;
+mod queue;
+
+pub use lease::Lease;
+pub use queue::LeasedQueue;
@@ -0,0 +1,379 @@
+use crate::{
+    ConcurrentQueue,
+    leased::{delivery::Delivery, lease::Lease},
+};
+#[cfg(feature = "std")]
+use crate::{leased::delivery::Expiring, lock_utils::lock_ignoring_poison};
+#[cfg(feature = "std")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::{
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// Key of a lease with a timeout; leases are ordered by their deadlines.
+#[cfg(feature = "std")]
+pub(super) type LeaseKey = (Instant, u64);
+
+/// A concurrent queue providing at-least-once delivery of its elements.
+///
+/// Elements are popped by [`pop_leased`] wrapped in a [`Lease`] rather than being moved out of the queue for good:
+///
+/// * calling [`ack`] on the lease finalizes the delivery and returns the element,
+/// * calling [`nack`] on the lease or dropping it without an ack puts the element back to the queue for redelivery.
+///
+/// Since the lease is also dropped while unwinding, an element is not lost when the worker processing it panics.
+///
+/// The queue can be created with a maximum number of deliveries.
+/// Once an element has been delivered this many times without being acknowledged, it is moved to the
+/// [`dead_letters`] queue rather than being redelivered.
+///
+/// With the `std` feature, elements can also be leased with a timeout by [`pop_leased_for`], which keeps a clone
+/// of the element until the lease is acknowledged or dropped.
+/// Once the timeout passes, the clone is redelivered so that a hung worker cannot hold on to an element forever.
+///
+/// [`pop_leased`]: crate::LeasedQueue::pop_leased
+/// [`pop_leased_for`]: crate::LeasedQueue::pop_leased_for
+/// [`dead_letters`]: crate::LeasedQueue::dead_letters
+/// [`ack`]: crate::Lease::ack
+/// [`nack`]: crate::Lease::nack
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::LeasedQueue;
+///
+/// let queue = LeasedQueue::with_max_deliveries(2);
+/// queue.extend(['a', 'b']);
+///
+/// let lease = queue.pop_leased().unwrap();
+/// assert_eq!(*lease, 'a');
+/// assert_eq!(lease.ack(), 'a');
+///
+/// let lease = queue.pop_leased().unwrap();
+/// assert_eq!(*lease, 'b');
+/// drop(lease); // failed: b will be redelivered
+///
+/// let lease = queue.pop_leased().unwrap();
+/// assert_eq!((*lease, lease.num_deliveries()), ('b', 2));
+/// lease.nack(); // failed again: maximum number of deliveries is reached
+///
+/// assert!(queue.pop_leased().is_none());
+/// assert_eq!(queue.dead_letters().pop(), Some('b'));
+/// ```
+///
+/// The following example demonstrates a job queue where workers might panic in the middle of a job.
+///
+/// ```
+/// use orx_concurrent_queue::LeasedQueue;
+/// use std::panic::{catch_unwind, AssertUnwindSafe};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let queue = LeasedQueue::new();
+/// queue.extend(0..100);
+///
+/// let sum = AtomicUsize::new(0);
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             while let Some(job) = queue.pop_leased() {
+///                 // crashes while processing job 42 for the first time
+///                 let crashed = catch_unwind(AssertUnwindSafe(|| {
+///                     assert!(*job != 42 || job.num_deliveries() > 1);
+///                     sum.fetch_add(*job, Ordering::Relaxed);
+///                 }));
+///                 match crashed {
+///                     Ok(()) => _ = job.ack(),
+///                     Err(_) => drop(job), // job 42 is redelivered
+///                 }
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(sum.into_inner(), 99 * 100 / 2);
+/// ```
+pub struct LeasedQueue<T>
+where
+    T: Send,
+{
+    queue: ConcurrentQueue<Delivery<T>>,
+    dead_letters: ConcurrentQueue<T>,
+    max_deliveries: usize,
+    #[cfg(feature = "std")]
+    expiring: Mutex<BTreeMap<LeaseKey, Expiring<T>>>,
+    #[cfg(feature = "std")]
+    num_expiring: AtomicUsize,
+    #[cfg(feature = "std")]
+    next_lease_id: AtomicU64,
+}
+
+impl<T> Default for LeasedQueue<T>
+where
+    T: Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LeasedQueue<T>
+where
+    T: Send,
+{
+    /// Creates a new empty leased queue which redelivers elements until they are acknowledged,
+    /// without any limit on the number of deliveries.
+    pub fn new() -> Self {
+        Self::with_max_deliveries(usize::MAX)
+    }
+
+    /// Creates a new empty leased queue where each element is delivered at most `max_deliveries` times.
+    ///
+    /// When a lease of an element which has already been delivered `max_deliveries` times is not acknowledged,
+    /// the element is moved to the [`dead_letters`] queue.
+    ///
+    /// [`dead_letters`]: crate::LeasedQueue::dead_letters
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_deliveries` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::LeasedQueue;
+    ///
+    /// let queue = LeasedQueue::with_max_deliveries(1);
+    /// queue.push(42);
+    ///
+    /// let lease = queue.pop_leased().unwrap();
+    /// lease.nack();
+    ///
+    /// assert!(queue.is_empty());
+    /// assert_eq!(queue.dead_letters().pop(), Some(42));
+    /// ```
+    pub fn with_max_deliveries(max_deliveries: usize) -> Self {
+        assert!(
+            max_deliveries > 0,
+            "Maximum number of deliveries must be positive."
+        );
+        Self {
+            queue: ConcurrentQueue::new(),
+            dead_letters: ConcurrentQueue::new(),
+            max_deliveries,
+            #[cfg(feature = "std")]
+            expiring: Mutex::new(BTreeMap::new()),
+            #[cfg(feature = "std")]
+            num_expiring: 0.into(),
+            #[cfg(feature = "std")]
+            next_lease_id: 0.into(),
+        }
+    }
+
+    /// Returns the maximum number of times an element is delivered.
+    pub fn max_deliveries(&self) -> usize {
+        self.max_deliveries
+    }
+
+    /// Returns the queue of elements which reached the maximum number of deliveries without being acknowledged.
+    ///
+    /// Elements are pushed to the dead letters queue in the order they are given up.
+    pub fn dead_letters(&self) -> &ConcurrentQueue<T> {
+        &self.dead_letters
+    }
+
+    // grow
+
+    /// Pushes the `value` to the back of the queue.
+    pub fn push(&self, value: T) {
+        self.queue.push(Delivery {
+            value,
+            num_deliveries: 0,
+        });
+    }
+
+    /// Extends the queue by pushing `values` elements to the back of the queue.
+    pub fn extend<I, Iter>(&self, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        self.queue.extend(values.into_iter().map(|value| Delivery {
+            value,
+            num_deliveries: 0,
+        }));
+    }
+
+    // shrink
+
+    /// Pops the element in the front of the queue and returns it wrapped in a [`Lease`];
+    /// returns None if the queue is empty.
+    ///
+    /// The element is put back to the queue unless the lease is acknowledged by [`ack`].
+    ///
+    /// With the `std` feature, expired leases created by [`pop_leased_for`] are redelivered before popping.
+    ///
+    /// [`ack`]: crate::Lease::ack
+    /// [`pop_leased_for`]: crate::LeasedQueue::pop_leased_for
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::LeasedQueue;
+    ///
+    /// let queue = LeasedQueue::new();
+    /// queue.extend([1, 2]);
+    ///
+    /// let lease = queue.pop_leased().unwrap();
+    /// assert_eq!(*lease, 1);
+    /// drop(lease);
+    ///
+    /// assert_eq!(queue.pop_leased().map(|x| x.ack()), Some(2));
+    /// assert_eq!(queue.pop_leased().map(|x| x.ack()), Some(1));
+    /// assert!(queue.pop_leased().is_none());
+    /// ```
+    pub fn pop_leased(&self) -> Option<Lease<'_, T>> {
+        #[cfg(feature = "std")]
+        self.redeliver_expired();
+
+        self.queue.pop().map(|x| {
+            let num_deliveries = x.num_deliveries + 1;
+            Lease::new(self, x.value, num_deliveries)
+        })
+    }
+
+    // get
+
+    /// Returns the number of elements waiting to be delivered.
+    ///
+    /// Leased elements which are not yet acknowledged are not counted.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if there is no element waiting to be delivered, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    // helpers
+
+    pub(super) fn redeliver(&self, value: T, num_deliveries: usize) {
+        match num_deliveries < self.max_deliveries {
+            true => self.queue.push(Delivery {
+                value,
+                num_deliveries,
+            }),
+            false => self.dead_letters.push(value),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> LeasedQueue<T>
+where
+    T: Send,
+{
+    /// Pops the element in the front of the queue and returns it wrapped in a [`Lease`] which expires after the `timeout`;
+    /// returns None if the queue is empty.
+    ///
+    /// The queue keeps a clone of the element until the lease is acknowledged or dropped.
+    /// If the lease is still alive when the `timeout` passes, the clone is redelivered by the next call to
+    /// [`redeliver_expired`], [`pop_leased`] or [`pop_leased_for`].
+    /// The expired lease can still be used; however, dropping it does not put the element back to the queue once more.
+    ///
+    /// [`redeliver_expired`]: crate::LeasedQueue::redeliver_expired
+    /// [`pop_leased`]: crate::LeasedQueue::pop_leased
+    /// [`pop_leased_for`]: crate::LeasedQueue::pop_leased_for
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::LeasedQueue;
+    /// use std::time::Duration;
+    ///
+    /// let queue = LeasedQueue::new();
+    /// queue.push(42);
+    ///
+    /// // the worker hangs
+    /// let hung = queue.pop_leased_for(Duration::from_millis(10)).unwrap();
+    /// assert!(queue.pop_leased().is_none());
+    ///
+    /// std::thread::sleep(Duration::from_millis(10));
+    ///
+    /// let lease = queue.pop_leased().unwrap();
+    /// assert_eq!((*lease, lease.num_deliveries()), (42, 2));
+    /// assert_eq!(lease.ack(), 42);
+    ///
+    /// drop(hung);
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn pop_leased_for(&self, timeout: Duration) -> Option<Lease<'_, T>>
+    where
+        T: Clone,
+    {
+        self.redeliver_expired();
+
+        self.queue.pop().map(|x| {
+            let num_deliveries = x.num_deliveries + 1;
+            let key = (
+                Instant::now() + timeout,
+                self.next_lease_id.fetch_add(1, Ordering::Relaxed),
+            );
+            let clone = Expiring {
+                value: x.value.clone(),
+                num_deliveries,
+            };
+            self.lock_expiring().insert(key, clone);
+            self.num_expiring.fetch_add(1, Ordering::Release);
+            Lease::with_timeout(self, x.value, num_deliveries, key)
+        })
+    }
+
+    /// Redelivers the elements of the leases which expired before being acknowledged or dropped;
+    /// returns the number of redelivered elements.
+    ///
+    /// Note that expired leases are also redelivered by [`pop_leased`] and [`pop_leased_for`].
+    ///
+    /// [`pop_leased`]: crate::LeasedQueue::pop_leased
+    /// [`pop_leased_for`]: crate::LeasedQueue::pop_leased_for
+    pub fn redeliver_expired(&self) -> usize {
+        if self.num_expiring.load(Ordering::Acquire) == 0 {
+            return 0;
+        }
+
+        let now = Instant::now();
+        let mut expiring = self.lock_expiring();
+        let mut count = 0;
+        while let Some(entry) = expiring.first_entry() {
+            match entry.key().0 <= now {
+                true => {
+                    let x = entry.remove();
+                    self.num_expiring.fetch_sub(1, Ordering::Release);
+                    self.redeliver(x.value, x.num_deliveries);
+                    count += 1;
+                }
+                false => break,
+            }
+        }
+        count
+    }
+
+    /// Releases the clone kept for the lease with the given `key`;
+    /// returns false if the lease has already expired and its element has been redelivered.
+    pub(super) fn release_expiring(&self, key: &LeaseKey) -> bool {
+        let released = self.lock_expiring().remove(key).is_some();
+        if released {
+            self.num_expiring.fetch_sub(1, Ordering::Release);
+        }
+        released
+    }
+
+    fn lock_expiring(&self) -> MutexGuard<'_, BTreeMap<LeaseKey, Expiring<T>>> {
+        lock_ignoring_poison(&self.expiring)
+    }
+}
@@ -12,17 +12,58 @@
 )]
 #![no_std]
 
-#[cfg(test)]
 extern crate alloc;
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 #[cfg(test)]
 mod tests;
 
+mod allocation_policy;
 mod atomic_utils;
+#[cfg(feature = "std")]
+mod broadcast;
+mod builder;
+mod chunk_policy;
 mod common_traits;
+mod concurrent_log;
+#[cfg(feature = "std")]
+mod dedup_queue;
+#[cfg(feature = "std")]
+mod delay_queue;
+mod leased;
+#[cfg(feature = "std")]
+mod lock_utils;
+#[cfg(feature = "std")]
+mod ordered_collector;
+#[cfg(feature = "std")]
+mod partitioned;
+mod priority_queue;
 mod queue;
+mod reserve_error;
+mod sharded_queue;
+mod wait_strategy;
+mod work_stealing;
 mod write_permit;
 
+pub use allocation_policy::AllocationPolicy;
+#[cfg(feature = "std")]
+pub use broadcast::{BroadcastQueue, Subscriber};
+pub use builder::{Backend, ConcurrentQueueBuilder, DoublingGrowth, FixedCapacity, LinearGrowth};
+pub use chunk_policy::{ChunkPolicy, PullMetrics};
 pub use common_traits::iter;
+pub use concurrent_log::ConcurrentLog;
+#[cfg(feature = "std")]
+pub use dedup_queue::{DedupConcurrentQueue, DedupPolicy};
+#[cfg(feature = "std")]
+pub use delay_queue::{Clock, DelayQueue, ManualClock, SystemClock};
+pub use leased::{Lease, LeasedQueue};
+#[cfg(feature = "std")]
+pub use ordered_collector::OrderedCollector;
+#[cfg(feature = "std")]
+pub use partitioned::{Consumer, PartitionedQueue};
+pub use priority_queue::ConcurrentPriorityQueue;
 pub use queue::{ConcurrentQueue, DefaultConPinnedVec};
+pub use reserve_error::ReserveMaxCapacityError;
+pub use sharded_queue::ShardedConcurrentQueue;
+pub use wait_strategy::WaitStrategy;
+pub use work_stealing::{WorkStealingOwner, WorkStealingQueue};
@@ -0,0 +1,11 @@
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Locks the `mutex`, and returns its guard even if the mutex is poisoned by a thread which panicked while holding it.
+///
+/// Every mutex of the crate protects a state which is updated by single calls to the methods of the std collections,
+/// such as inserting into a map or pushing to a heap, which leave the collection valid even if a user-provided
+/// implementation such as `Hash` or `Ord` panics. Since the state is never left inconsistent, the poisoning flag
+/// does not carry any information and can be ignored.
+pub(crate) fn lock_ignoring_poison<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
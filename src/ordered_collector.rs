@@ -0,0 +1,372 @@
+use crate::lock_utils::lock_ignoring_poison;
+use alloc::collections::VecDeque;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// A thread safe reorder buffer which accepts results tagged with their indices from multiple threads
+/// and yields them in strict index order.
+///
+/// It is the companion of the [`pop_with_idx`] and [`pull_with_idx`] methods of the [`ConcurrentQueue`]:
+/// workers pop elements together with their indices, process them in parallel, and push the results
+/// to the collector with the same indices, finishing in an arbitrary order.
+/// The consumer of the collector receives the results in the order of the indices.
+///
+/// * Results are pushed by [`push`] or, for consecutive indices such as the results of a pulled chunk, by [`extend`].
+/// * Results are received in order by [`pop`], [`pop_wait`] or [`iter`].
+/// * Once all producers are done, [`close`] wakes up the waiting consumer.
+///
+/// # Bounded buffering
+///
+/// The collector buffers at most `capacity` results: a result can be pushed only if its index is within
+/// the window `next_idx..next_idx + capacity`, where `next_idx` is the index of the next result to be received.
+/// A producer pushing a result beyond the window is blocked until the consumer moves the window forward.
+/// This applies backpressure on the producers which run too far ahead of the slowest one.
+///
+/// The result at `next_idx` always fits in the window; hence, the producers cannot block each other indefinitely
+/// as long as every index is eventually pushed.
+///
+/// [`pop_with_idx`]: crate::ConcurrentQueue::pop_with_idx
+/// [`pull_with_idx`]: crate::ConcurrentQueue::pull_with_idx
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+/// [`push`]: crate::OrderedCollector::push
+/// [`extend`]: crate::OrderedCollector::extend
+/// [`pop`]: crate::OrderedCollector::pop
+/// [`pop_wait`]: crate::OrderedCollector::pop_wait
+/// [`iter`]: crate::OrderedCollector::iter
+/// [`close`]: crate::OrderedCollector::close
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::OrderedCollector;
+///
+/// let collector = OrderedCollector::new(4);
+///
+/// collector.push(1, 'b');
+/// assert_eq!(collector.pop(), None); // waiting for index 0
+///
+/// collector.push(0, 'a');
+/// collector.push(2, 'c');
+/// assert_eq!(collector.pop(), Some('a'));
+/// assert_eq!(collector.pop(), Some('b'));
+/// assert_eq!(collector.pop(), Some('c'));
+/// assert_eq!(collector.pop(), None);
+/// ```
+///
+/// The following example builds an order preserving parallel map on top of a [`ConcurrentQueue`].
+///
+/// ```
+/// use orx_concurrent_queue::{ConcurrentQueue, OrderedCollector};
+///
+/// let queue = ConcurrentQueue::new();
+/// queue.extend(0..1000);
+///
+/// let collector = OrderedCollector::new(64);
+/// let mut output = vec![];
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         std::thread::scope(|s| {
+///             for _ in 0..4 {
+///                 s.spawn(|| {
+///                     while let Some((begin_idx, chunk)) = queue.pull_with_idx(8) {
+///                         collector.extend(begin_idx, chunk.map(|x| x * 2));
+///                     }
+///                 });
+///             }
+///         });
+///         collector.close();
+///     });
+///
+///     output.extend(collector.iter());
+/// });
+///
+/// assert_eq!(output, (0..1000).map(|x| x * 2).collect::<Vec<_>>());
+/// ```
+pub struct OrderedCollector<R> {
+    state: Mutex<State<R>>,
+    capacity: usize,
+    next_ready: Condvar,
+    window_moved: Condvar,
+}
+
+struct State<R> {
+    buffer: VecDeque<Option<R>>,
+    next_idx: usize,
+    closed: bool,
+}
+
+impl<R> OrderedCollector<R> {
+    /// Creates a new empty collector which expects the first result with index zero and buffers at most `capacity` results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_first_idx(capacity, 0)
+    }
+
+    /// Creates a new empty collector which expects the first result with index `first_idx` and buffers at most `capacity` results.
+    ///
+    /// This is useful when the queue had already been popped before the collector is created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::OrderedCollector;
+    ///
+    /// let collector = OrderedCollector::with_first_idx(8, 100);
+    /// collector.push(101, 'b');
+    /// collector.push(100, 'a');
+    ///
+    /// assert_eq!(collector.pop(), Some('a'));
+    /// assert_eq!(collector.next_idx(), 101);
+    /// ```
+    pub fn with_first_idx(capacity: usize, first_idx: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "Capacity of the ordered collector must be positive."
+        );
+        Self {
+            state: Mutex::new(State {
+                buffer: VecDeque::with_capacity(capacity),
+                next_idx: first_idx,
+                closed: false,
+            }),
+            capacity,
+            next_ready: Condvar::new(),
+            window_moved: Condvar::new(),
+        }
+    }
+
+    /// Returns the maximum number of results that the collector buffers.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // grow
+
+    /// Pushes the `result` with the given index `idx` to the collector.
+    ///
+    /// If `idx` is beyond the buffering window, the calling thread is blocked until the consumer receives
+    /// enough results to move the window forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result with index `idx` has already been pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::OrderedCollector;
+    ///
+    /// let collector = OrderedCollector::new(2);
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         // blocks until 'a' is received
+    ///         collector.push(2, 'c');
+    ///     });
+    ///
+    ///     collector.push(1, 'b');
+    ///     collector.push(0, 'a');
+    ///     assert_eq!(collector.pop_wait(), Some('a'));
+    ///     assert_eq!(collector.pop_wait(), Some('b'));
+    ///     assert_eq!(collector.pop_wait(), Some('c'));
+    /// });
+    /// ```
+    pub fn push(&self, idx: usize, result: R) {
+        let mut state = self.lock();
+        while idx >= state.next_idx + self.capacity {
+            state = self
+                .window_moved
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        self.insert(&mut state, idx, result);
+        let is_next = idx == state.next_idx;
+        drop(state);
+
+        if is_next {
+            self.next_ready.notify_all();
+        }
+    }
+
+    /// Pushes the `result` with the given index `idx` to the collector only if `idx` is within the buffering window;
+    /// otherwise, immediately returns back the result as the error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result with index `idx` has already been pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::OrderedCollector;
+    ///
+    /// let collector = OrderedCollector::new(2);
+    ///
+    /// assert_eq!(collector.try_push(1, 'b'), Ok(()));
+    /// assert_eq!(collector.try_push(2, 'c'), Err('c'));
+    ///
+    /// assert_eq!(collector.try_push(0, 'a'), Ok(()));
+    /// assert_eq!(collector.pop(), Some('a'));
+    /// assert_eq!(collector.try_push(2, 'c'), Ok(()));
+    /// ```
+    pub fn try_push(&self, idx: usize, result: R) -> Result<(), R> {
+        let mut state = self.lock();
+        if idx >= state.next_idx + self.capacity {
+            return Err(result);
+        }
+        self.insert(&mut state, idx, result);
+        let is_next = idx == state.next_idx;
+        drop(state);
+
+        if is_next {
+            self.next_ready.notify_all();
+        }
+        Ok(())
+    }
+
+    /// Pushes the `results` with consecutive indices starting from `begin_idx` to the collector.
+    ///
+    /// This is convenient for pushing the results of a chunk obtained by [`pull_with_idx`].
+    ///
+    /// [`pull_with_idx`]: crate::ConcurrentQueue::pull_with_idx
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result of any of the indices has already been pushed.
+    pub fn extend(&self, begin_idx: usize, results: impl IntoIterator<Item = R>) {
+        for (i, result) in results.into_iter().enumerate() {
+            self.push(begin_idx + i, result);
+        }
+    }
+
+    /// Marks that all results are pushed, waking up the consumer waiting in [`pop_wait`].
+    ///
+    /// After the collector is closed, [`pop_wait`] returns None instead of blocking when the next result is not available.
+    ///
+    /// [`pop_wait`]: crate::OrderedCollector::pop_wait
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.next_ready.notify_all();
+    }
+
+    // shrink
+
+    /// Returns the result with index `next_idx` and moves the window forward;
+    /// returns None if this result is not pushed yet.
+    pub fn pop(&self) -> Option<R> {
+        let mut state = self.lock();
+        let result = Self::take_next(&mut state);
+        drop(state);
+
+        if result.is_some() {
+            self.window_moved.notify_all();
+        }
+        result
+    }
+
+    /// Returns the result with index `next_idx` and moves the window forward, blocking the calling thread
+    /// until this result is pushed; returns None if the collector is closed and the result is not pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::OrderedCollector;
+    ///
+    /// let collector = OrderedCollector::new(4);
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         collector.push(1, 'b');
+    ///         collector.push(0, 'a');
+    ///         collector.close();
+    ///     });
+    ///
+    ///     assert_eq!(collector.pop_wait(), Some('a'));
+    ///     assert_eq!(collector.pop_wait(), Some('b'));
+    ///     assert_eq!(collector.pop_wait(), None);
+    /// });
+    /// ```
+    pub fn pop_wait(&self) -> Option<R> {
+        let mut state = self.lock();
+        let result = loop {
+            match Self::take_next(&mut state) {
+                Some(x) => break Some(x),
+                None if state.closed => break None,
+                None => {
+                    state = self
+                        .next_ready
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner)
+                }
+            }
+        };
+        drop(state);
+
+        if result.is_some() {
+            self.window_moved.notify_all();
+        }
+        result
+    }
+
+    /// Returns an iterator yielding the results in index order by [`pop_wait`], which ends once the collector is closed
+    /// and the next result is not pushed.
+    ///
+    /// [`pop_wait`]: crate::OrderedCollector::pop_wait
+    pub fn iter(&self) -> impl Iterator<Item = R> + '_ {
+        core::iter::from_fn(|| self.pop_wait())
+    }
+
+    // get
+
+    /// Returns the index of the next result to be received.
+    pub fn next_idx(&self) -> usize {
+        self.lock().next_idx
+    }
+
+    /// Returns the number of results which are pushed but not yet received.
+    pub fn num_buffered(&self) -> usize {
+        self.lock().buffer.iter().filter(|x| x.is_some()).count()
+    }
+
+    /// Returns true if the collector is closed, false otherwise.
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    // helpers
+
+    fn insert(&self, state: &mut State<R>, idx: usize, result: R) {
+        assert!(
+            idx >= state.next_idx,
+            "Result with index {idx} is already received."
+        );
+        let position = idx - state.next_idx;
+        if state.buffer.len() <= position {
+            state.buffer.resize_with(position + 1, || None);
+        }
+        let slot = &mut state.buffer[position];
+        assert!(slot.is_none(), "Result with index {idx} is already pushed.");
+        *slot = Some(result);
+    }
+
+    fn take_next(state: &mut State<R>) -> Option<R> {
+        match state.buffer.front().is_some_and(|x| x.is_some()) {
+            true => {
+                state.next_idx += 1;
+                state.buffer.pop_front().flatten()
+            }
+            false => None,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<R>> {
+        lock_ignoring_poison(&self.state)
+    }
+}
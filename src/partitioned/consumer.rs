@@ -0,0 +1,149 @@
+use crate::{PartitionedQueue, common_traits::iter::QueueIterOwned};
+use core::hash::{BuildHasher, Hash};
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// A member of the consumer group of a [`PartitionedQueue`].
+///
+/// A consumer claims a partition when it pops an element from it and holds the claim until its next
+/// [`pop`] or [`pull`] call, or until it is dropped.
+/// While the claim is held, no other consumer can receive elements of the same partition;
+/// hence, elements with the same key are never processed concurrently or out of order.
+///
+/// In other words, calling [`pop`] or [`pull`] again marks the previously received elements as processed.
+/// Every call starts searching from the partition following the previously claimed one, so that the
+/// consumer visits all partitions in a round-robin order.
+///
+/// The consumer is created by [`PartitionedQueue::consumer`].
+///
+/// [`PartitionedQueue`]: crate::PartitionedQueue
+/// [`PartitionedQueue::consumer`]: crate::PartitionedQueue::consumer
+/// [`pop`]: crate::Consumer::pop
+/// [`pull`]: crate::Consumer::pull
+pub struct Consumer<'a, K, T, S, P>
+where
+    K: Hash + ?Sized,
+    T: Send,
+    S: BuildHasher,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: &'a PartitionedQueue<K, T, S, P>,
+    claimed: Option<usize>,
+    next_partition: usize,
+}
+
+impl<K, T, S, P> Drop for Consumer<'_, K, T, S, P>
+where
+    K: Hash + ?Sized,
+    T: Send,
+    S: BuildHasher,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl<'a, K, T, S, P> Consumer<'a, K, T, S, P>
+where
+    K: Hash + ?Sized,
+    T: Send,
+    S: BuildHasher,
+    P: ConcurrentPinnedVec<T>,
+{
+    pub(super) fn new(queue: &'a PartitionedQueue<K, T, S, P>, first_partition: usize) -> Self {
+        Self {
+            queue,
+            claimed: None,
+            next_partition: first_partition,
+        }
+    }
+
+    /// Releases the partition claimed by the previous call, and pops an element from the next partition
+    /// which is neither empty nor claimed by another consumer; returns None if there is no such partition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::PartitionedQueue;
+    ///
+    /// let queue = PartitionedQueue::new(4);
+    /// queue.extend(&"x", [1, 2]);
+    ///
+    /// let mut c1 = queue.consumer();
+    /// let mut c2 = queue.consumer();
+    ///
+    /// // c1 holds the partition of "x" until its next call
+    /// assert_eq!(c1.pop(), Some(1));
+    /// assert_eq!(c2.pop(), None);
+    ///
+    /// assert_eq!(c1.pop(), Some(2));
+    /// assert_eq!(c1.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.release();
+        let queue = self.queue;
+        self.claim_next(|p| queue.partition(p).pop())
+    }
+
+    /// Releases the partition claimed by the previous call, and pulls a chunk of at most `chunk_size` consecutive
+    /// elements from the next partition which is neither empty nor claimed by another consumer;
+    /// returns None if there is no such partition.
+    ///
+    /// Elements of the chunk are returned in the order they are pushed.
+    /// The partition remains claimed while the chunk is being consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::PartitionedQueue;
+    ///
+    /// let queue = PartitionedQueue::new(2);
+    /// queue.extend(&7, 0..5);
+    ///
+    /// let mut consumer = queue.consumer();
+    /// assert_eq!(consumer.pull(3).map(|x| x.collect::<Vec<_>>()), Some(vec![0, 1, 2]));
+    /// assert_eq!(consumer.pull(3).map(|x| x.collect::<Vec<_>>()), Some(vec![3, 4]));
+    /// assert!(consumer.pull(3).is_none());
+    /// ```
+    pub fn pull(&mut self, chunk_size: usize) -> Option<QueueIterOwned<'_, T, P>> {
+        self.release();
+        let queue: &PartitionedQueue<K, T, S, P> = self.queue;
+        self.claim_next(|p| queue.partition(p).pull(chunk_size))
+    }
+
+    /// Returns the index of the partition currently claimed by this consumer, if any.
+    pub fn claimed_partition(&self) -> Option<usize> {
+        self.claimed
+    }
+
+    /// Releases the partition currently claimed by this consumer, if any.
+    ///
+    /// This allows other consumers to receive elements of the partition without waiting for the next
+    /// call of this consumer.
+    pub fn release(&mut self) {
+        if let Some(p) = self.claimed.take() {
+            self.queue.partition(p).release();
+        }
+    }
+
+    // helpers
+
+    fn claim_next<O>(&mut self, mut take: impl FnMut(usize) -> Option<O>) -> Option<O> {
+        let n = self.queue.num_partitions();
+        for i in 0..n {
+            let p = (self.next_partition + i) % n;
+            let partition = self.queue.partition(p);
+            if !partition.is_empty() && partition.try_claim() {
+                match take(p) {
+                    Some(x) => {
+                        self.claimed = Some(p);
+                        self.next_partition = (p + 1) % n;
+                        return Some(x);
+                    }
+                    None => partition.release(),
+                }
+            }
+        }
+        None
+    }
+}
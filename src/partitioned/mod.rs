@@ -0,0 +1,5 @@
+mod consumer;
+mod queue;
+
+pub use consumer::Consumer;
+pub use queue::PartitionedQueue;
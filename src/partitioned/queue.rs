@@ -0,0 +1,337 @@
+use crate::{ConcurrentQueue, DefaultConPinnedVec, partitioned::Consumer};
+use alloc::vec::Vec;
+use core::{
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    ops::Deref,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use orx_pinned_vec::ConcurrentPinnedVec;
+use std::hash::RandomState;
+
+/// A thread safe queue composed of multiple partitions, where every element is pushed together with a key
+/// and elements of the same key always go to the same partition.
+///
+/// Elements are consumed by the members of a consumer group, each of which is a [`Consumer`] created by [`consumer`].
+/// Every partition is consumed by at most one consumer at a time:
+/// * a consumer claims a partition when it pops an element from it,
+/// * and it holds the claim until it pops the next element or it is dropped.
+///
+/// Therefore, elements with the same key are processed in the order they are pushed, while elements with
+/// different keys are processed in parallel.
+///
+/// The partition of a key is determined by its hash, using the hasher of the queue which is [`RandomState`] by default.
+///
+/// [`Consumer`]: crate::Consumer
+/// [`consumer`]: crate::PartitionedQueue::consumer
+/// [`RandomState`]: std::hash::RandomState
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::PartitionedQueue;
+///
+/// let queue = PartitionedQueue::new(4);
+///
+/// queue.push(&"alice", 1);
+/// queue.push(&"bob", 10);
+/// queue.push(&"alice", 2);
+///
+/// let mut consumer = queue.consumer();
+/// let mut alice = vec![];
+/// while let Some(x) = consumer.pop() {
+///     if x < 10 {
+///         alice.push(x);
+///     }
+/// }
+///
+/// assert_eq!(alice, vec![1, 2]);
+/// assert!(queue.is_empty());
+/// ```
+///
+/// The following example processes transactions of accounts in parallel while the transactions of each account
+/// are processed in order.
+///
+/// ```
+/// use orx_concurrent_queue::PartitionedQueue;
+/// use std::sync::Mutex;
+///
+/// let num_accounts = 8;
+/// let queue = PartitionedQueue::new(4);
+/// for i in 0..800 {
+///     queue.push(&(i % num_accounts), (i % num_accounts, i));
+/// }
+///
+/// let processed: Vec<_> = (0..num_accounts).map(|_| Mutex::new(vec![])).collect();
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..3 {
+///         let mut consumer = queue.consumer();
+///         let processed = &processed;
+///         s.spawn(move || {
+///             while let Some((account, i)) = consumer.pop() {
+///                 processed[account].lock().unwrap().push(i);
+///             }
+///         });
+///     }
+/// });
+///
+/// for (account, transactions) in processed.into_iter().enumerate() {
+///     let transactions = transactions.into_inner().unwrap();
+///     let expected: Vec<_> = (0..100).map(|j| j * num_accounts + account).collect();
+///     assert_eq!(transactions, expected);
+/// }
+/// ```
+pub struct PartitionedQueue<K, T, S = RandomState, P = DefaultConPinnedVec<T>>
+where
+    K: Hash + ?Sized,
+    T: Send,
+    S: BuildHasher,
+    P: ConcurrentPinnedVec<T>,
+{
+    partitions: Vec<Partition<T, P>>,
+    hasher: S,
+    next_consumer: AtomicUsize,
+    phantom: PhantomData<fn(&K)>,
+}
+
+impl<K, T> PartitionedQueue<K, T, RandomState, DefaultConPinnedVec<T>>
+where
+    K: Hash + ?Sized,
+    T: Send,
+{
+    /// Creates a new empty partitioned queue with `num_partitions` partitions using the default hasher.
+    ///
+    /// Each partition is backed with default concurrent pinned vec, which is the concurrent version of [`SplitVec`] with [`Doubling`] growth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_partitions` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::PartitionedQueue;
+    ///
+    /// let queue: PartitionedQueue<u64, String> = PartitionedQueue::new(16);
+    /// assert_eq!(queue.num_partitions(), 16);
+    /// ```
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Doubling`]: orx_split_vec::Doubling
+    pub fn new(num_partitions: usize) -> Self {
+        Self::with_hasher(num_partitions, RandomState::new())
+    }
+}
+
+impl<K, T, S> PartitionedQueue<K, T, S, DefaultConPinnedVec<T>>
+where
+    K: Hash + ?Sized,
+    T: Send,
+    S: BuildHasher,
+{
+    /// Creates a new empty partitioned queue with `num_partitions` partitions which uses the given `hasher`
+    /// to determine the partitions of the keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_partitions` is zero.
+    pub fn with_hasher(num_partitions: usize, hasher: S) -> Self {
+        Self::from_partitions((0..num_partitions).map(|_| ConcurrentQueue::new()), hasher)
+    }
+}
+
+impl<K, T, S, P> PartitionedQueue<K, T, S, P>
+where
+    K: Hash + ?Sized,
+    T: Send,
+    S: BuildHasher,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Creates a partitioned queue using the given `partitions` and `hasher`.
+    ///
+    /// Partitions might be empty or might already contain elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partitions` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{ConcurrentQueue, PartitionedQueue};
+    /// use std::hash::RandomState;
+    ///
+    /// let queue: PartitionedQueue<str, _, _, _> = PartitionedQueue::from_partitions(
+    ///     (0..4).map(|_| ConcurrentQueue::with_linear_growth(10, 64)),
+    ///     RandomState::new(),
+    /// );
+    /// queue.push("key", 'a');
+    /// assert_eq!(queue.num_partitions(), 4);
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn from_partitions(
+        partitions: impl IntoIterator<Item = ConcurrentQueue<T, P>>,
+        hasher: S,
+    ) -> Self {
+        let partitions: Vec<_> = partitions.into_iter().map(Partition::new).collect();
+        assert!(
+            !partitions.is_empty(),
+            "Partitioned queue must have at least one partition."
+        );
+        Self {
+            partitions,
+            hasher,
+            next_consumer: 0.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of partitions of the queue.
+    pub fn num_partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Returns the index of the partition that elements with the given `key` are pushed to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::PartitionedQueue;
+    ///
+    /// let queue = PartitionedQueue::new(4);
+    /// queue.push(&42, 'x');
+    ///
+    /// let p = queue.partition_of(&42);
+    /// assert!(p < 4);
+    /// assert_eq!(queue.len_of(p), 1);
+    /// ```
+    pub fn partition_of(&self, key: &K) -> usize {
+        (self.hasher.hash_one(key) % self.partitions.len() as u64) as usize
+    }
+
+    /// Creates a new member of the consumer group of this queue.
+    ///
+    /// Consumers start searching for elements from different partitions in order to spread over the partitions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::PartitionedQueue;
+    ///
+    /// let queue = PartitionedQueue::new(2);
+    /// queue.push(&1, 'a');
+    ///
+    /// let mut consumer = queue.consumer();
+    /// assert_eq!(consumer.pop(), Some('a'));
+    /// assert_eq!(consumer.pop(), None);
+    /// ```
+    pub fn consumer(&self) -> Consumer<'_, K, T, S, P> {
+        let first_partition = self.next_consumer.fetch_add(1, Ordering::Relaxed);
+        Consumer::new(self, first_partition % self.partitions.len())
+    }
+
+    // grow
+
+    /// Pushes the `value` to the back of the partition of the given `key`.
+    pub fn push(&self, key: &K, value: T) {
+        self.partitions[self.partition_of(key)].push(value)
+    }
+
+    /// Extends the partition of the given `key` by pushing `values` elements to its back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::PartitionedQueue;
+    ///
+    /// let queue = PartitionedQueue::new(3);
+    /// queue.extend(&'k', [1, 2, 3]);
+    ///
+    /// assert_eq!(queue.len_of(queue.partition_of(&'k')), 3);
+    /// ```
+    pub fn extend<I, Iter>(&self, key: &K, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        self.partitions[self.partition_of(key)].extend(values)
+    }
+
+    // get
+
+    /// Returns the total number of elements in all partitions.
+    pub fn len(&self) -> usize {
+        self.partitions.iter().map(|x| x.len()).sum()
+    }
+
+    /// Returns the number of elements in the partition at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partition` is out of bounds.
+    pub fn len_of(&self, partition: usize) -> usize {
+        self.partitions[partition].len()
+    }
+
+    /// Returns true if all partitions are empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.partitions.iter().all(|x| x.is_empty())
+    }
+
+    // helpers
+
+    #[inline(always)]
+    pub(super) fn partition(&self, idx: usize) -> &Partition<T, P> {
+        &self.partitions[idx]
+    }
+}
+
+/// A partition of the queue with the flag of the consumer claim, aligned to a separate cache line in order to
+/// avoid false sharing between the neighboring partitions.
+#[repr(align(128))]
+pub(super) struct Partition<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: ConcurrentQueue<T, P>,
+    claimed: AtomicBool,
+}
+
+impl<T, P> Partition<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn new(queue: ConcurrentQueue<T, P>) -> Self {
+        Self {
+            queue,
+            claimed: false.into(),
+        }
+    }
+
+    /// Claims the partition; returns false if it is already claimed by another consumer.
+    pub(super) fn try_claim(&self) -> bool {
+        self.claimed
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Releases the claim; elements popped while holding the claim happen-before the next claim.
+    pub(super) fn release(&self) {
+        self.claimed.store(false, Ordering::Release);
+    }
+}
+
+impl<T, P> Deref for Partition<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    type Target = ConcurrentQueue<T, P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.queue
+    }
+}
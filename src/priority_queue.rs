@@ -0,0 +1,339 @@
+use crate::{ConcurrentQueue, DefaultConPinnedVec, common_traits::iter::QueueIterOwned};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// A concurrent priority queue with a fixed number of priority levels, `LEVELS`.
+///
+/// * Every element is pushed with a priority in `0..LEVELS`; the greater the priority, the sooner the element is served.
+/// * [`pop`] and [`pull`] always serve the highest priority level which is not empty.
+/// * Elements of the same priority level are served in FIFO order.
+///
+/// Each level is a [`ConcurrentQueue`]; therefore, the priority queue can concurrently grow and shrink with a shared reference.
+///
+/// Additionally, the priority queue keeps track of the total number of elements in all levels with a single counter.
+/// This counter is incremented before an element is pushed and decremented after it is popped.
+/// Therefore, [`is_empty`] returns true only if all levels are empty and no push is in progress,
+/// which makes it a reliable termination check for a scheduler which repeatedly pops and pushes tasks.
+///
+/// [`pop`]: crate::ConcurrentPriorityQueue::pop
+/// [`pull`]: crate::ConcurrentPriorityQueue::pull
+/// [`is_empty`]: crate::ConcurrentPriorityQueue::is_empty
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::ConcurrentPriorityQueue;
+///
+/// let queue: ConcurrentPriorityQueue<_, 3> = ConcurrentPriorityQueue::new();
+///
+/// queue.push(0, 'a');
+/// queue.push(2, 'b');
+/// queue.push(1, 'c');
+/// queue.push(2, 'd');
+///
+/// assert_eq!(queue.len(), 4);
+///
+/// assert_eq!(queue.pop(), Some('b'));
+/// assert_eq!(queue.pop(), Some('d'));
+/// assert_eq!(queue.pop(), Some('c'));
+/// assert_eq!(queue.pop(), Some('a'));
+/// assert_eq!(queue.pop(), None);
+/// ```
+pub struct ConcurrentPriorityQueue<T, const LEVELS: usize, P = DefaultConPinnedVec<T>>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    levels: [ConcurrentQueue<T, P>; LEVELS],
+    len: AtomicUsize,
+}
+
+impl<T, const LEVELS: usize> Default for ConcurrentPriorityQueue<T, LEVELS, DefaultConPinnedVec<T>>
+where
+    T: Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const LEVELS: usize> ConcurrentPriorityQueue<T, LEVELS, DefaultConPinnedVec<T>>
+where
+    T: Send,
+{
+    /// Creates a new empty concurrent priority queue with `LEVELS` priority levels.
+    ///
+    /// Each level is backed with default concurrent pinned vec, which is the concurrent version of [`SplitVec`] with [`Doubling`] growth.
+    ///
+    /// In order to create levels backed with a particular [`PinnedVec`], you may use [`from_levels`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<String, 4> = ConcurrentPriorityQueue::new();
+    /// assert!(queue.is_empty());
+    /// ```
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Doubling`]: orx_split_vec::Doubling
+    /// [`PinnedVec`]: orx_pinned_vec::PinnedVec
+    /// [`from_levels`]: crate::ConcurrentPriorityQueue::from_levels
+    pub fn new() -> Self {
+        Self::from_levels(core::array::from_fn(|_| ConcurrentQueue::new()))
+    }
+}
+
+impl<T, const LEVELS: usize, P> ConcurrentPriorityQueue<T, LEVELS, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Creates a concurrent priority queue from the queues of each of the priority levels,
+    /// where `levels[p]` is the queue of priority `p`.
+    ///
+    /// Levels might be empty or might already contain elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{ConcurrentQueue, ConcurrentPriorityQueue};
+    ///
+    /// let low = ConcurrentQueue::with_fixed_capacity(16);
+    /// low.extend([1, 2]);
+    /// let high = ConcurrentQueue::with_fixed_capacity(16);
+    /// high.extend([3]);
+    ///
+    /// let queue = ConcurrentPriorityQueue::from_levels([low, high]);
+    ///
+    /// assert_eq!(queue.len(), 3);
+    /// assert_eq!(queue.pop(), Some(3));
+    /// assert_eq!(queue.pop(), Some(1));
+    /// ```
+    pub fn from_levels(levels: [ConcurrentQueue<T, P>; LEVELS]) -> Self {
+        const { assert!(LEVELS > 0, "Priority queue must have at least one level.") };
+        let len = levels.iter().map(|x| x.len()).sum::<usize>();
+        Self {
+            levels,
+            len: len.into(),
+        }
+    }
+
+    /// Consumes the priority queue and returns the queues of each of the priority levels,
+    /// where the `p`-th queue holds the elements with priority `p`.
+    pub fn into_levels(self) -> [ConcurrentQueue<T, P>; LEVELS] {
+        self.levels
+    }
+
+    // shrink
+
+    /// Pops and returns the element in the front of the highest priority level which is not empty;
+    /// returns None if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<_, 2> = ConcurrentPriorityQueue::new();
+    ///
+    /// queue.extend(0, [1, 2]);
+    /// queue.push(1, 3);
+    ///
+    /// assert_eq!(queue.pop(), Some(3));
+    /// assert_eq!(queue.pop(), Some(1));
+    /// assert_eq!(queue.pop(), Some(2));
+    /// assert_eq!(queue.pop(), None);
+    /// ```
+    pub fn pop(&self) -> Option<T> {
+        self.pop_with_priority().map(|x| x.1)
+    }
+
+    /// Pops and returns the element in the front of the highest priority level which is not empty together with its priority;
+    /// returns None if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<_, 2> = ConcurrentPriorityQueue::new();
+    ///
+    /// queue.push(0, 'a');
+    /// queue.push(1, 'b');
+    ///
+    /// assert_eq!(queue.pop_with_priority(), Some((1, 'b')));
+    /// assert_eq!(queue.pop_with_priority(), Some((0, 'a')));
+    /// assert_eq!(queue.pop_with_priority(), None);
+    /// ```
+    pub fn pop_with_priority(&self) -> Option<(usize, T)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let (priority, value) = self
+            .levels
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(p, x)| x.pop().map(|x| (p, x)))?;
+        self.len.fetch_sub(1, Ordering::Release);
+        Some((priority, value))
+    }
+
+    /// Pulls `chunk_size` elements from the front of the highest priority level which is not empty:
+    ///
+    /// * returns None if `chunk_size` is zero,
+    /// * returns None if the queue is empty,
+    /// * otherwise, returns Some of the priority and a non-empty ExactSizeIterator of at most `chunk_size` elements.
+    ///
+    /// All pulled elements belong to the same priority level, which is the first element of the returned tuple.
+    /// Even if the level has fewer than `chunk_size` elements, the chunk is not completed with elements of the lower levels.
+    ///
+    /// Pulled elements are guaranteed to be consecutive elements of the level, see [`ConcurrentQueue::pull`].
+    ///
+    /// [`ConcurrentQueue::pull`]: crate::ConcurrentQueue::pull
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<_, 3> = ConcurrentPriorityQueue::new();
+    ///
+    /// queue.extend(0, 0..3);
+    /// queue.extend(2, 3..5);
+    ///
+    /// let pull = |n| queue.pull(n).map(|(p, x)| (p, x.collect::<Vec<_>>()));
+    ///
+    /// assert_eq!(pull(3), Some((2, vec![3, 4])));
+    /// assert_eq!(pull(2), Some((0, vec![0, 1])));
+    /// assert_eq!(pull(2), Some((0, vec![2])));
+    /// assert_eq!(pull(2), None);
+    /// ```
+    pub fn pull(&self, chunk_size: usize) -> Option<(usize, QueueIterOwned<'_, T, P>)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let (priority, chunk) = self
+            .levels
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(p, x)| x.pull(chunk_size).map(|x| (p, x)))?;
+        self.len.fetch_sub(chunk.len(), Ordering::Release);
+        Some((priority, chunk))
+    }
+
+    // grow
+
+    /// Pushes the `value` to the back of the level with the given `priority`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is not less than `LEVELS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<_, 2> = ConcurrentPriorityQueue::new();
+    ///
+    /// queue.push(1, 'a');
+    /// queue.push(1, 'b');
+    /// queue.push(0, 'c');
+    ///
+    /// assert_eq!(queue.len(), 3);
+    /// assert_eq!(queue.len_of(1), 2);
+    /// ```
+    pub fn push(&self, priority: usize, value: T) {
+        let level = self.level(priority);
+        self.len.fetch_add(1, Ordering::Acquire);
+        level.push(value);
+    }
+
+    /// Extends the level with the given `priority` by pushing `values` elements to its back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is not less than `LEVELS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<_, 2> = ConcurrentPriorityQueue::new();
+    ///
+    /// queue.extend(0, 0..4);
+    /// queue.extend(1, vec![4, 5]);
+    ///
+    /// assert_eq!(queue.len(), 6);
+    /// assert_eq!(queue.len_of(0), 4);
+    /// ```
+    pub fn extend<I, Iter>(&self, priority: usize, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        let level = self.level(priority);
+        let values = values.into_iter();
+        self.len.fetch_add(values.len(), Ordering::Acquire);
+        level.extend(values);
+    }
+
+    // get
+
+    /// Returns the total number of elements in all levels of the queue.
+    ///
+    /// Note that elements which are being concurrently pushed are also counted.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of elements with the given `priority`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is not less than `LEVELS`.
+    pub fn len_of(&self, priority: usize) -> usize {
+        self.level(priority).len()
+    }
+
+    /// Returns true if the queue is empty, false otherwise.
+    ///
+    /// Unlike checking emptiness of every level one by one, this method does not race across the levels:
+    /// it returns true only if all levels are empty and no element is being concurrently pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentPriorityQueue;
+    ///
+    /// let queue: ConcurrentPriorityQueue<_, 2> = ConcurrentPriorityQueue::new();
+    /// assert!(queue.is_empty());
+    ///
+    /// queue.push(1, 42);
+    /// assert!(!queue.is_empty());
+    ///
+    /// _ = queue.pop();
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len.load(Ordering::Acquire) == 0
+    }
+
+    // helpers
+
+    fn level(&self, priority: usize) -> &ConcurrentQueue<T, P> {
+        assert!(
+            priority < LEVELS,
+            "Priority must be less than the number of levels of the priority queue."
+        );
+        &self.levels[priority]
+    }
+}
@@ -1,15 +1,24 @@
 use crate::{
-    atomic_utils::{comp_exch, comp_exch_weak},
-    common_traits::iter::{QueueIterOfMut, QueueIterOfRef, QueueIterOwned},
+    allocation_policy::AllocationPolicy,
+    atomic_utils::{FlagGuard, Readers, comp_exch, comp_exch_seq_cst, comp_exch_weak},
+    builder::ConcurrentQueueBuilder,
+    chunk_policy::{ChunkPolicy, PullCounters, PullMetrics},
+    common_traits::iter::{
+        PulledSlices, QueueDrain, QueueIterOfMut, QueueIterOfRef, QueueIterOwned,
+        QueueIterReturnable,
+    },
+    reserve_error::ReserveMaxCapacityError,
+    wait_strategy::WaitStrategy,
     write_permit::WritePermit,
 };
+use alloc::{collections::VecDeque, string::ToString, vec::Vec};
 use core::{
     marker::PhantomData,
-    ops::Range,
-    sync::atomic::{AtomicUsize, Ordering},
+    ops::{Bound, Range, RangeBounds},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
 };
 use orx_fixed_vec::{ConcurrentFixedVec, FixedVec};
-use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec, PinnedVec};
 use orx_split_vec::{ConcurrentSplitVec, Doubling, Linear, SplitVec, prelude::PseudoDefault};
 
 type DefaultPinnedVec<T> = SplitVec<T, Doubling>;
@@ -84,6 +93,117 @@ where
     pub fn with_doubling_growth() -> Self {
         SplitVec::with_doubling_growth_and_max_concurrent_capacity().into()
     }
+
+    /// Creates a builder which allows to configure the backend, capacity and pulling behavior of the queue.
+    ///
+    /// See [`ConcurrentQueueBuilder`] for details.
+    ///
+    /// [`ConcurrentQueueBuilder`]: crate::ConcurrentQueueBuilder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::builder()
+    ///     .linear_growth(1000) // fragments of 1024 elements
+    ///     .initial_capacity(2000)
+    ///     .max_capacity(100_000)
+    ///     .build();
+    ///
+    /// queue.extend(0..2000);
+    /// assert_eq!(queue.len(), 2000);
+    /// ```
+    pub fn builder() -> ConcurrentQueueBuilder<T> {
+        ConcurrentQueueBuilder::new()
+    }
+
+    /// Creates a new concurrent queue backed with the default concurrent pinned vec containing the elements
+    /// of the `vec` in order.
+    ///
+    /// Elements are moved into the fragments of the split vector.
+    /// In order to reuse the allocation of the vector instead, the queue can be created from a [`FixedVec`]
+    /// as `FixedVec::from(vec).into()`; note that such a queue cannot grow beyond the capacity of the vector.
+    ///
+    /// This is an inherent method rather than a `From<Vec<T>>` implementation, which would conflict with the
+    /// blanket `From<P>` implementation for pinned vectors: coherence rules do not allow it, since the upstream
+    /// crates might implement `IntoConcurrentPinnedVec` for `Vec<T>` in the future.
+    ///
+    /// [`FixedVec`]: orx_fixed_vec::FixedVec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use orx_fixed_vec::FixedVec;
+    ///
+    /// let queue = ConcurrentQueue::from_vec(vec![1, 2, 3]);
+    /// assert_eq!(queue.pop(), Some(1));
+    /// queue.push(4);
+    /// assert_eq!(queue.into_vec(), vec![2, 3, 4]);
+    ///
+    /// // reusing the allocation
+    /// let mut vec = Vec::with_capacity(4);
+    /// vec.extend([1, 2, 3]);
+    /// let queue: ConcurrentQueue<_, _> = FixedVec::from(vec).into();
+    /// queue.push(4);
+    /// assert_eq!(queue.into_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        let queue = Self::new();
+        queue.extend(vec);
+        queue
+    }
+
+    /// Creates a new concurrent queue backed with the default concurrent pinned vec containing the elements
+    /// of the `deque` in order from its front to its back.
+    ///
+    /// `From<VecDeque<T>>` is not implemented for the same coherence reason as `From<Vec<T>>`;
+    /// see [`from_vec`].
+    ///
+    /// [`from_vec`]: crate::ConcurrentQueue::from_vec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut deque = VecDeque::from(vec![2, 3]);
+    /// deque.push_front(1);
+    ///
+    /// let queue = ConcurrentQueue::from_vec_deque(deque);
+    /// assert_eq!(queue.pop(), Some(1));
+    /// assert_eq!(queue.into_vec_deque(), VecDeque::from(vec![2, 3]));
+    /// ```
+    pub fn from_vec_deque(deque: VecDeque<T>) -> Self {
+        let queue = Self::new();
+        queue.extend(deque);
+        queue
+    }
+
+    /// Creates a new concurrent queue backed with the default concurrent pinned vec containing the elements
+    /// of the `array` in order.
+    ///
+    /// Provided as an inherent method since a `From<[T; N]>` implementation would overlap with the blanket
+    /// `From<P>` implementation for pinned vectors; see [`from_vec`].
+    ///
+    /// [`from_vec`]: crate::ConcurrentQueue::from_vec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::from_array(['a', 'b', 'c']);
+    /// assert_eq!(queue.pop(), Some('a'));
+    /// assert_eq!(queue.len(), 2);
+    /// ```
+    pub fn from_array<const N: usize>(array: [T; N]) -> Self {
+        let queue = Self::new();
+        queue.extend(array);
+        queue
+    }
 }
 
 impl<T> ConcurrentQueue<T, ConcurrentFixedVec<T>>
@@ -278,6 +398,13 @@ where
     written: AtomicUsize,
     write_reserved: AtomicUsize,
     popped: AtomicUsize,
+    chunk_policy: ChunkPolicy,
+    pull_counters: PullCounters,
+    allocation_policy: AllocationPolicy,
+    wait_strategy: WaitStrategy,
+    growing: AtomicBool,
+    grown_ahead_from: AtomicUsize,
+    readers: Readers,
 }
 
 unsafe impl<T, P> Sync for ConcurrentQueue<T, P>
@@ -316,6 +443,13 @@ where
             written: vec.len().into(),
             write_reserved: vec.len().into(),
             popped: 0.into(),
+            chunk_policy: ChunkPolicy::default(),
+            pull_counters: PullCounters::default(),
+            allocation_policy: AllocationPolicy::default(),
+            wait_strategy: WaitStrategy::default(),
+            growing: false.into(),
+            grown_ahead_from: 0.into(),
+            readers: Readers::default(),
             vec: vec.into_concurrent(),
         }
     }
@@ -354,6 +488,119 @@ where
     /// assert_eq!(vec, vec![5, 6]);
     /// ```
     pub fn into_inner(mut self) -> <P as ConcurrentPinnedVec<T>>::P
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+    {
+        self.take_inner()
+    }
+
+    /// Converts the queue into a vector of the elements in the queue in order.
+    ///
+    /// If no element has been popped, the queue is converted into its underlying pinned vector without moving the
+    /// elements, which is then converted into a vector; this conversion reuses the allocation of a [`FixedVec`].
+    /// Otherwise, each element in the queue is moved once to the created vector.
+    ///
+    /// [`FixedVec`]: orx_fixed_vec::FixedVec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::with_fixed_capacity(8);
+    /// queue.extend(0..6);
+    /// _ = queue.pull(2);
+    ///
+    /// assert_eq!(queue.into_vec(), vec![2, 3, 4, 5]);
+    /// ```
+    pub fn into_vec(mut self) -> Vec<T>
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P> + Into<Vec<T>>,
+    {
+        match self.popped.load(Ordering::Relaxed) {
+            0 => self.take_inner().into(),
+            _ => self.into_iter().collect(),
+        }
+    }
+
+    /// Converts the queue into a double-ended queue of the elements in the queue in order.
+    ///
+    /// The conversion is equivalent to converting [`into_vec`] and then into a `VecDeque`, which does not move
+    /// the elements.
+    ///
+    /// [`into_vec`]: crate::ConcurrentQueue::into_vec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..4);
+    /// _ = queue.pop();
+    ///
+    /// let mut deque = queue.into_vec_deque();
+    /// assert_eq!(deque.pop_back(), Some(3));
+    /// assert_eq!(deque.pop_front(), Some(1));
+    /// ```
+    pub fn into_vec_deque(self) -> VecDeque<T>
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P> + Into<Vec<T>>,
+    {
+        self.into_vec().into()
+    }
+
+    /// Moves the elements of the queue to the front of the underlying storage, resets the positions of the queue,
+    /// and releases the memory which is not required to store the elements in the queue.
+    ///
+    /// Positions of popped elements are never reused by the queue.
+    /// Therefore, after heavy use, the elements in the queue sit at the end of a large storage.
+    /// Compacting allows to reuse the same queue across processing rounds without reallocating the entire storage,
+    /// and keeps the positions away from overflowing.
+    ///
+    /// Note that a queue backed by a [`FixedVec`] keeps its entire capacity; however, its positions are reset.
+    ///
+    /// [`FixedVec`]: orx_fixed_vec::FixedVec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    ///
+    /// queue.extend(0..1000);
+    /// _ = queue.pull(997);
+    /// assert_eq!(queue.num_write_reserved(Ordering::Relaxed), 1000);
+    /// assert!(queue.capacity() >= 1000);
+    ///
+    /// queue.compact();
+    /// assert_eq!(queue.num_write_reserved(Ordering::Relaxed), 3);
+    /// assert!(queue.capacity() < 1000);
+    /// assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![997, 998, 999]);
+    ///
+    /// queue.push(1000);
+    /// assert_eq!(queue.pop(), Some(997));
+    /// ```
+    pub fn compact(&mut self)
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+    {
+        let vec = self.take_inner();
+        let len = vec.len();
+        self.vec = vec.into_concurrent();
+
+        self.written.store(len, Ordering::Relaxed);
+        self.write_reserved.store(len, Ordering::Relaxed);
+    }
+
+    /// Takes out the elements of the queue as a pinned vector, leaving the queue empty with an empty storage.
+    fn take_inner(&mut self) -> <P as ConcurrentPinnedVec<T>>::P
     where
         <P as ConcurrentPinnedVec<T>>::P:
             PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
@@ -373,7 +620,12 @@ where
             }
         }
 
-        for x in [&self.written, &self.write_reserved, &self.popped] {
+        for x in [
+            &self.written,
+            &self.write_reserved,
+            &self.popped,
+            &self.grown_ahead_from,
+        ] {
             x.store(0, Ordering::Relaxed);
         }
 
@@ -398,12 +650,15 @@ where
     /// assert_eq!(queue.pop(), None);
     /// ```
     pub fn pop(&self) -> Option<T> {
-        let idx = self.popped.fetch_add(1, Ordering::Relaxed);
+        let idx = self.popped.fetch_add(1, Ordering::SeqCst);
 
         loop {
             let written = self.written.load(Ordering::Acquire);
             match idx < written {
-                true => return Some(unsafe { self.ptr(idx).read() }),
+                true => {
+                    self.readers.wait_for_readers_of(idx..(idx + 1));
+                    return Some(unsafe { self.ptr(idx).read() });
+                }
                 false => {
                     if comp_exch(&self.popped, idx + 1, idx).is_ok() {
                         return None;
@@ -446,42 +701,10 @@ where
     /// assert_eq!(queue.pull(1).map(|x| x.collect::<Vec<_>>()), None);
     /// ```
     pub fn pull(&self, chunk_size: usize) -> Option<QueueIterOwned<'_, T, P>> {
-        match chunk_size > 0 {
-            true => {
-                let begin_idx = self.popped.fetch_add(chunk_size, Ordering::Relaxed);
-                let end_idx = begin_idx + chunk_size;
-
-                loop {
-                    let written = self.written.load(Ordering::Acquire);
-
-                    let has_none = begin_idx >= written;
-                    let has_some = !has_none;
-                    let has_all = end_idx <= written;
-
-                    let range = match (has_some, has_all) {
-                        (false, _) => match comp_exch(&self.popped, end_idx, begin_idx).is_ok() {
-                            true => return None,
-                            false => None,
-                        },
-                        (true, true) => Some(begin_idx..end_idx),
-                        (true, false) => Some(begin_idx..written),
-                    };
-
-                    if let Some(range) = range {
-                        let ok = match has_all {
-                            true => true,
-                            false => comp_exch(&self.popped, end_idx, range.end).is_ok(),
-                        };
-
-                        if ok {
-                            let iter = unsafe { self.vec.ptr_iter_unchecked(range) };
-                            return Some(QueueIterOwned::new(iter));
-                        }
-                    }
-                }
-            }
-            false => None,
-        }
+        self.pull_range(chunk_size).map(|range| {
+            let iter = unsafe { self.vec.ptr_iter_unchecked(range) };
+            QueueIterOwned::new(iter)
+        })
     }
 
     // shrink with idx
@@ -503,12 +726,15 @@ where
     /// assert_eq!(queue.pop_with_idx(), None);
     /// ```
     pub fn pop_with_idx(&self) -> Option<(usize, T)> {
-        let idx = self.popped.fetch_add(1, Ordering::Relaxed);
+        let idx = self.popped.fetch_add(1, Ordering::SeqCst);
 
         loop {
             let written = self.written.load(Ordering::Acquire);
             match idx < written {
-                true => return Some((idx, unsafe { self.ptr(idx).read() })),
+                true => {
+                    self.readers.wait_for_readers_of(idx..(idx + 1));
+                    return Some((idx, unsafe { self.ptr(idx).read() }));
+                }
                 false => {
                     if comp_exch(&self.popped, idx + 1, idx).is_ok() {
                         return None;
@@ -552,86 +778,166 @@ where
     /// assert_eq!(queue.pull_with_idx(1).map(|(i, x)| x.enumerate().map(|(j, x)| (i + j, x)).collect::<Vec<_>>()), None);
     /// ```
     pub fn pull_with_idx(&self, chunk_size: usize) -> Option<(usize, QueueIterOwned<'_, T, P>)> {
-        match chunk_size > 0 {
-            true => {
-                let begin_idx = self.popped.fetch_add(chunk_size, Ordering::Relaxed);
-                let end_idx = begin_idx + chunk_size;
+        self.pull_range(chunk_size).map(|range| {
+            let begin_idx = range.start;
+            let iter = unsafe { self.vec.ptr_iter_unchecked(range) };
+            (begin_idx, QueueIterOwned::new(iter))
+        })
+    }
 
-                loop {
-                    let written = self.written.load(Ordering::Acquire);
+    // shrink in batches
 
-                    let has_none = begin_idx >= written;
-                    let has_some = !has_none;
-                    let has_all = end_idx <= written;
+    /// Pulls a chunk of consecutive elements from the front of the queue, where the chunk size is determined by the
+    /// [`ChunkPolicy`] of the queue depending on the current length of the queue:
+    ///
+    /// * returns Some of a non-empty ExactSizeIterator with `len` at most the chunk size if the queue is not empty,
+    /// * returns None if the queue is empty.
+    ///
+    /// Every call is recorded in the [`pull_metrics`] of the queue which can be used to tune the policy.
+    ///
+    /// [`ChunkPolicy`]: crate::ChunkPolicy
+    /// [`pull_metrics`]: crate::ConcurrentQueue::pull_metrics
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{ChunkPolicy, ConcurrentQueue};
+    ///
+    /// let queue = ConcurrentQueue::new().with_chunk_policy(ChunkPolicy::guided(2));
+    /// queue.extend(0..8);
+    ///
+    /// // chunks get smaller as the queue gets shorter
+    /// assert_eq!(queue.pull_adaptive().map(|x| x.len()), Some(4));
+    /// assert_eq!(queue.pull_adaptive().map(|x| x.len()), Some(2));
+    /// assert_eq!(queue.pull_adaptive().map(|x| x.len()), Some(1));
+    /// assert_eq!(queue.pull_adaptive().map(|x| x.len()), Some(1));
+    /// assert!(queue.pull_adaptive().is_none());
+    ///
+    /// let metrics = queue.pull_metrics();
+    /// assert_eq!(metrics.num_pulls, 4);
+    /// assert_eq!(metrics.num_empty_pulls, 1);
+    /// assert_eq!(metrics.num_pulled, 8);
+    /// assert_eq!(metrics.max_chunk_size, 4);
+    /// ```
+    pub fn pull_adaptive(&self) -> Option<QueueIterOwned<'_, T, P>> {
+        let chunk_size = self.chunk_policy.chunk_size(self.len());
+        let range = self.pull_range(chunk_size);
+        self.pull_counters.record(range.as_ref().map(|x| x.len()));
+        range.map(|range| {
+            let iter = unsafe { self.vec.ptr_iter_unchecked(range) };
+            QueueIterOwned::new(iter)
+        })
+    }
 
-                    let range = match (has_some, has_all) {
-                        (false, _) => match comp_exch(&self.popped, end_idx, begin_idx).is_ok() {
-                            true => return None,
-                            false => None,
-                        },
-                        (true, true) => Some(begin_idx..end_idx),
-                        (true, false) => Some(begin_idx..written),
-                    };
+    /// Returns the chunk policy used by [`pull_adaptive`].
+    ///
+    /// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+    pub fn chunk_policy(&self) -> ChunkPolicy {
+        self.chunk_policy
+    }
 
-                    if let Some(range) = range {
-                        let ok = match has_all {
-                            true => true,
-                            false => comp_exch(&self.popped, end_idx, range.end).is_ok(),
-                        };
+    /// Sets the chunk policy used by [`pull_adaptive`].
+    ///
+    /// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+    pub fn set_chunk_policy(&mut self, chunk_policy: ChunkPolicy) {
+        self.chunk_policy = chunk_policy;
+    }
 
-                        if ok {
-                            let iter = unsafe { self.vec.ptr_iter_unchecked(range) };
-                            return Some((begin_idx, QueueIterOwned::new(iter)));
-                        }
-                    }
-                }
-            }
-            false => None,
-        }
+    /// Returns the queue with the given chunk policy used by [`pull_adaptive`].
+    ///
+    /// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+    pub fn with_chunk_policy(mut self, chunk_policy: ChunkPolicy) -> Self {
+        self.chunk_policy = chunk_policy;
+        self
     }
 
-    // grow
+    /// Returns the statistics of the [`pull_adaptive`] calls since the queue is created or the metrics are reset.
+    ///
+    /// Statistics are not collected, and hence, all zeros if the queue is built with pull metrics disabled
+    /// (see [`ConcurrentQueueBuilder::pull_metrics`]).
+    ///
+    /// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+    /// [`ConcurrentQueueBuilder::pull_metrics`]: crate::ConcurrentQueueBuilder::pull_metrics
+    pub fn pull_metrics(&self) -> PullMetrics {
+        self.pull_counters.metrics()
+    }
 
-    /// Pushes the `value` to the back of the queue.
+    /// Resets the statistics of the [`pull_adaptive`] calls.
+    ///
+    /// [`pull_adaptive`]: crate::ConcurrentQueue::pull_adaptive
+    pub fn reset_pull_metrics(&self) {
+        self.pull_counters.reset()
+    }
+
+    /// Returns the queue which collects the [`pull_metrics`] only if `enabled` is true.
+    ///
+    /// [`pull_metrics`]: crate::ConcurrentQueue::pull_metrics
+    pub(crate) fn with_pull_metrics(mut self, enabled: bool) -> Self {
+        self.pull_counters = PullCounters::new(enabled);
+        self
+    }
+
+    /// Pulls `chunk_size` consecutive elements from the front of the queue exactly as [`pull`]; however, returns the
+    /// pulled chunk as a [`PulledSlices`] which provides access to the elements as contiguous slices:
+    ///
+    /// * returns None if `chunk_size` is zero,
+    /// * returns Some of a chunk with `len = chunk_size` if the queue has at least `chunk_size` items,
+    /// * returns Some of a non-empty chunk with `len` such that `0 < len < chunk_size` if the queue
+    ///   has `len` elements,
+    /// * returns None if the queue is empty.
+    ///
+    /// Since the storage of the queue is a pinned vector, the chunk is made up of at most a few slices,
+    /// which allows for zero-copy batch processing such as `copy_from_slice`, `sort_unstable` or SIMD.
+    ///
+    /// [`pull`]: crate::ConcurrentQueue::pull
+    /// [`PulledSlices`]: crate::iter::PulledSlices
     ///
     /// # Examples
     ///
     /// ```
-    /// use orx_concurrent_queue::*;
+    /// use orx_concurrent_queue::ConcurrentQueue;
     ///
     /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..100);
     ///
-    /// queue.push(1);
-    /// queue.push(2);
-    /// queue.push(3);
-    /// assert_eq!(queue.into_inner(), vec![1, 2, 3]);
+    /// let mut sum = 0;
+    /// while let Some(chunk) = queue.pull_slices(16) {
+    ///     for slice in chunk.slices() {
+    ///         sum += slice.iter().sum::<i32>();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(sum, 4950);
     /// ```
-    pub fn push(&self, value: T) {
-        let idx = self.write_reserved.fetch_add(1, Ordering::Relaxed);
-        self.assert_has_capacity_for(idx);
-
-        loop {
-            match WritePermit::for_one(self.vec.capacity(), idx) {
-                WritePermit::JustWrite => {
-                    unsafe { self.ptr(idx).write(value) };
-                    break;
-                }
-                WritePermit::GrowThenWrite => {
-                    self.grow_to(idx + 1);
-                    unsafe { self.ptr(idx).write(value) };
-                    break;
-                }
-                WritePermit::Spin => {}
-            }
-        }
-
-        let num_written = idx + 1;
-        while comp_exch_weak(&self.written, idx, num_written).is_err() {}
+    pub fn pull_slices(&self, chunk_size: usize) -> Option<PulledSlices<'_, T, P>> {
+        self.pull_range(chunk_size).map(|range| {
+            let slices = unsafe { self.vec.slices_mut(range.clone()) };
+            let slices = slices.into_iter().collect();
+            // SAFETY: the range is pulled, and hence, exclusively owned by the chunk
+            unsafe { PulledSlices::new(self, range, slices) }
+        })
     }
 
-    /// Extends the queue by pushing `values` elements to the back of the queue.
+    /// Pulls `chunk_size` consecutive elements from the front of the queue exactly as [`pull`]; however, the elements
+    /// which are not consumed by the time the returned iterator is dropped are pushed back to the queue rather than being dropped:
     ///
-    /// In order to reduce the number of concurrent state updates, `extend` might be preferred over `push` whenever possible.
+    /// * returns None if `chunk_size` is zero,
+    /// * returns Some of an ExactSizeIterator with `len = chunk_size` if the queue has at least `chunk_size` items,
+    /// * returns Some of a non-empty ExactSizeIterator with `len` such that `0 < len < chunk_size` if the queue
+    ///   has `len` elements,
+    /// * returns None if the queue is empty.
+    ///
+    /// This guarantees that no work is lost when a consumer stops in the middle of a chunk.
+    /// Note that the returned elements are pushed to the back of the queue, and hence, they occupy new positions of
+    /// the underlying storage; a queue with a fixed capacity must have room for them, otherwise they are dropped.
+    /// In order to recover the elements which cannot be returned, use [`QueueIterReturnable::return_remaining`].
+    ///
+    /// [`QueueIterReturnable::return_remaining`]: crate::iter::QueueIterReturnable::return_remaining
+    ///
+    /// See [`QueueIterReturnable`] for details.
+    ///
+    /// [`pull`]: crate::ConcurrentQueue::pull
+    /// [`QueueIterReturnable`]: crate::iter::QueueIterReturnable
     ///
     /// # Examples
     ///
@@ -639,51 +945,453 @@ where
     /// use orx_concurrent_queue::ConcurrentQueue;
     ///
     /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..6);
     ///
-    /// queue.extend(1..3);
-    /// queue.extend(vec![3, 4, 5, 6]);
+    /// let mut chunk = queue.pull_returnable(4).unwrap();
+    /// assert_eq!(chunk.next(), Some(0));
+    /// assert_eq!(chunk.next(), Some(1));
+    /// drop(chunk); // stopped early, 2 and 3 are returned to the queue
     ///
-    /// assert_eq!(queue.into_inner(), vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(queue.into_inner(), vec![4, 5, 2, 3]);
     /// ```
-    pub fn extend<I, Iter>(&self, values: I)
-    where
-        I: IntoIterator<Item = T, IntoIter = Iter>,
-        Iter: ExactSizeIterator<Item = T>,
+    pub fn pull_returnable(&self, chunk_size: usize) -> Option<QueueIterReturnable<'_, T, P>> {
+        self.pull_range(chunk_size).map(|range| {
+            let iter = unsafe { self.vec.ptr_iter_unchecked(range) };
+            QueueIterReturnable::new(self, iter)
+        })
+    }
+
+    /// Pulls exactly `n` consecutive elements from the front of the queue only if the queue has at least `n` elements;
+    /// otherwise, leaves the queue untouched and returns None.
+    ///
+    /// * returns None if `n` is zero,
+    /// * returns Some of an ExactSizeIterator with `len = n` if at least `n` elements are committed,
+    /// * returns None otherwise.
+    ///
+    /// Unlike [`pull`], this method never returns a partial chunk.
+    /// Further, it never reserves and rolls back positions; the pull is performed with a single atomic
+    /// compare-exchange only if enough elements are available.
+    /// Therefore, it is safe to use concurrently with all other pushes, pops and pulls.
+    ///
+    /// [`pull`]: crate::ConcurrentQueue::pull
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..6);
+    ///
+    /// assert_eq!(queue.pull_exact(4).map(|x| x.collect::<Vec<_>>()), Some(vec![0, 1, 2, 3]));
+    ///
+    /// // only 2 elements left
+    /// assert!(queue.pull_exact(4).is_none());
+    /// assert_eq!(queue.len(), 2);
+    ///
+    /// queue.extend(6..8);
+    /// assert_eq!(queue.pull_exact(4).map(|x| x.collect::<Vec<_>>()), Some(vec![4, 5, 6, 7]));
+    /// ```
+    pub fn pull_exact(&self, n: usize) -> Option<QueueIterOwned<'_, T, P>> {
+        match n > 0 {
+            true => self.pull_available(n, n),
+            false => None,
+        }
+    }
+
+    /// Pulls consecutive elements from the front of the queue until their cumulative cost, computed by `cost`,
+    /// would exceed the `budget`:
+    ///
+    /// * returns Some of a non-empty ExactSizeIterator if the queue is not empty,
+    /// * returns None if the queue is empty.
+    ///
+    /// The first element is always pulled, even if its cost alone exceeds the `budget`, so that every element
+    /// is eventually pulled.
+    ///
+    /// Since costs are known only after reading the elements, the pull is performed in two steps.
+    /// First, the costs of the elements at the front of the queue are computed under a shared reference, without claiming them.
+    /// Then, only the elements within the budget are claimed with a single atomic compare-exchange, provided that
+    /// no other thread has popped from the queue in the meantime; otherwise, the costs are computed again.
+    /// Therefore, the elements are never moved out of their order, and concurrent pops and pulls always observe them in the queue.
+    /// Since multiple threads might compute the costs of the same elements, they are required to be `Sync`.
+    ///
+    /// This provides a better load balance than [`pull`] when the cost of processing the elements varies widely.
+    ///
+    /// [`pull`]: crate::ConcurrentQueue::pull
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend([3, 4, 2, 12, 1, 1]);
+    ///
+    /// let cost = |x: &usize| *x;
+    ///
+    /// assert_eq!(queue.pull_weighted(10, cost).map(|x| x.collect::<Vec<_>>()), Some(vec![3, 4, 2]));
+    /// // the first element is pulled even if it exceeds the budget
+    /// assert_eq!(queue.pull_weighted(10, cost).map(|x| x.collect::<Vec<_>>()), Some(vec![12]));
+    /// assert_eq!(queue.pull_weighted(10, cost).map(|x| x.collect::<Vec<_>>()), Some(vec![1, 1]));
+    /// assert!(queue.pull_weighted(10, cost).is_none());
+    /// ```
+    pub fn pull_weighted<C>(&self, budget: usize, mut cost: C) -> Option<QueueIterOwned<'_, T, P>>
+    where
+        T: Sync,
+        C: FnMut(&T) -> usize,
     {
-        let values = values.into_iter();
-        let num_items = values.len();
+        loop {
+            let guard = self.readers.register();
+            let written = self.written.load(Ordering::Acquire);
+            let begin_idx = self.popped.load(Ordering::SeqCst);
 
-        if num_items > 0 {
-            let begin_idx = self.write_reserved.fetch_add(num_items, Ordering::Relaxed);
-            let end_idx = begin_idx + num_items;
-            let last_idx = begin_idx + num_items - 1;
-            self.assert_has_capacity_for(last_idx);
+            // popped might temporarily exceed written while another thread reverts its pull
+            if begin_idx >= written {
+                return None;
+            }
+            guard.set_range(begin_idx..written);
+
+            // SAFETY: elements within begin_idx..written are written, and they cannot be moved out while the guard is alive
+            let mut total_cost = cost(unsafe { &*self.ptr(begin_idx) });
+            let mut cut_idx = begin_idx + 1;
+            while cut_idx < written {
+                let total = total_cost.saturating_add(cost(unsafe { &*self.ptr(cut_idx) }));
+                if total > budget {
+                    break;
+                }
+                total_cost = total;
+                cut_idx += 1;
+            }
+            drop(guard);
+
+            // the costs are valid only if none of the elements are pulled by other threads in the meantime
+            if comp_exch_seq_cst(&self.popped, begin_idx, cut_idx).is_ok() {
+                self.readers.wait_for_readers_of(begin_idx..cut_idx);
+                let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..cut_idx) };
+                return Some(QueueIterOwned::new(iter));
+            }
+        }
+    }
+
+    /// Pulls a batch of consecutive elements from the front of the queue, waiting up to `timeout` for the batch to fill:
+    ///
+    /// * returns `max` elements as soon as the queue has at least `max` elements,
+    /// * returns all available elements, between `min` and `max`, once the `timeout` expires,
+    /// * returns None if the queue still has fewer than `min` elements when the `timeout` expires.
+    ///
+    /// A batch is pulled only if it has at least `min` elements; otherwise, the queue is left untouched.
+    /// Therefore, the size of the returned batch is within `min..=max` even when other threads concurrently pull from the queue.
+    ///
+    /// The calling thread polls the queue while waiting, and waits in between according to the [`wait_strategy`]
+    /// of the queue, which by default yields and sleeps for short intervals.
+    ///
+    /// [`wait_strategy`]: crate::ConcurrentQueue::wait_strategy
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero or if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use std::time::Duration;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..10);
+    ///
+    /// // returns immediately since max elements are available
+    /// let batch = queue.pull_batch(2, 4, Duration::from_secs(10));
+    /// assert_eq!(batch.map(|x| x.collect::<Vec<_>>()), Some(vec![0, 1, 2, 3]));
+    ///
+    /// // returns 6 elements once the timeout expires
+    /// let batch = queue.pull_batch(2, 8, Duration::from_millis(10));
+    /// assert_eq!(batch.map(|x| x.len()), Some(6));
+    ///
+    /// // the queue never reaches min elements
+    /// queue.push(42);
+    /// let batch = queue.pull_batch(2, 8, Duration::from_millis(10));
+    /// assert!(batch.is_none());
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    ///
+    /// The following example demonstrates micro-batching of requests which are pushed concurrently.
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use std::time::Duration;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         for i in 0..100 {
+    ///             queue.push(i);
+    ///         }
+    ///     });
+    ///
+    ///     let mut num_received = 0;
+    ///     while num_received < 100 {
+    ///         if let Some(batch) = queue.pull_batch(1, 16, Duration::from_millis(5)) {
+    ///             assert!((1..=16).contains(&batch.len()));
+    ///             num_received += batch.len();
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn pull_batch(
+        &self,
+        min: usize,
+        max: usize,
+        timeout: std::time::Duration,
+    ) -> Option<QueueIterOwned<'_, T, P>> {
+        use std::time::Instant;
+
+        assert!(max > 0, "Maximum batch size must be positive.");
+        assert!(min <= max, "Minimum batch size cannot exceed the maximum.");
+        let min = min.max(1);
+
+        let deadline = Instant::now() + timeout;
+        let mut num_waits = 0;
+        loop {
+            if let Some(batch) = self.pull_available(max, max) {
+                return Some(batch);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return self.pull_available(min, max);
+            }
+
+            self.wait_strategy.wait(num_waits, deadline - now);
+            num_waits += 1;
+        }
+    }
+
+    // parallel
+
+    /// Maps all elements of the queue in parallel with `f` using `num_threads` threads, and returns the results
+    /// in the order of the elements in the queue.
+    ///
+    /// Each thread repeatedly pulls chunks of `chunk_size` elements with [`pull_with_idx`] and maps them.
+    /// The results of each chunk are directly written to the original positions of the chunk in the output using its `begin_idx`;
+    /// therefore, the output order matches the input order regardless of the order in which the threads complete,
+    /// without collecting or sorting the chunks.
+    ///
+    /// The method returns once the queue is empty.
+    /// See [`par_map_ordered_until`] in order to keep mapping elements which are concurrently pushed to the queue until a close signal.
+    ///
+    /// [`pull_with_idx`]: crate::ConcurrentQueue::pull_with_idx
+    /// [`par_map_ordered_until`]: crate::ConcurrentQueue::par_map_ordered_until
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_threads` or `chunk_size` is zero.
+    ///
+    /// Panics if `f` panics, in which case the results mapped so far are dropped.
+    ///
+    /// No other thread may pop from the queue during the call; see [`par_map_ordered_until`] for the consequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..100);
+    ///
+    /// let output = queue.par_map_ordered(4, 8, |x| x.to_string());
+    ///
+    /// assert_eq!(output, (0..100).map(|x| x.to_string()).collect::<Vec<_>>());
+    /// assert!(queue.is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn par_map_ordered<R, F>(&self, num_threads: usize, chunk_size: usize, f: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(T) -> R + Sync,
+    {
+        self.par_map_ordered_until(num_threads, chunk_size, || true, f)
+    }
+
+    /// Maps elements of the queue in parallel with `f` using `num_threads` threads, and returns the results
+    /// in the order of the elements in the queue.
+    ///
+    /// Unlike [`par_map_ordered`], elements can still be pushed to the queue while the map runs:
+    /// threads keep waiting for new elements until `is_closed` returns true, and the method returns once
+    /// the queue is closed and empty.
+    ///
+    /// [`par_map_ordered`]: crate::ConcurrentQueue::par_map_ordered
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_threads` or `chunk_size` is zero.
+    ///
+    /// Panics if `f` panics, in which case the results mapped so far are dropped.
+    ///
+    /// No other thread may pop from the queue during the call. A pop of another thread might be observed before
+    /// the first element pulled by the map, in which case the method panics and drops the results mapped so far.
+    /// Otherwise, the results of the elements popped by other threads are missing, and the method returns the
+    /// results of the mapped elements in their order in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// let closed = AtomicBool::new(false);
+    ///
+    /// let output = std::thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         for i in 0..1000 {
+    ///             queue.push(i);
+    ///         }
+    ///         closed.store(true, Ordering::Release);
+    ///     });
+    ///
+    ///     queue.par_map_ordered_until(4, 16, || closed.load(Ordering::Acquire), |x| x * 2)
+    /// });
+    ///
+    /// assert_eq!(output, (0..1000).map(|x| x * 2).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn par_map_ordered_until<R, F, C>(
+        &self,
+        num_threads: usize,
+        chunk_size: usize,
+        is_closed: C,
+        f: F,
+    ) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(T) -> R + Sync,
+        C: Fn() -> bool + Sync,
+    {
+        assert!(num_threads > 0, "Number of threads must be positive.");
+        assert!(chunk_size > 0, "Chunk size must be positive.");
+
+        // results are written to the output at the positions of their elements relative to the first pulled position
+        let first_idx = self.popped.load(Ordering::Relaxed);
+        let output = ConcurrentQueue::<R>::new();
 
+        let map_chunks = || {
+            let mut mapped = MappedRanges::new(&output);
             loop {
-                match WritePermit::for_many(self.vec.capacity(), begin_idx, last_idx) {
-                    WritePermit::JustWrite => {
-                        let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..end_idx) };
-                        for (p, value) in iter.zip(values) {
-                            unsafe { p.write(value) };
+                // the signal must be read before pulling: elements pushed before the close are then observed by the pull
+                let closed = is_closed();
+                match self.pull_with_idx(chunk_size) {
+                    Some((begin_idx, chunk)) => {
+                        let begin = begin_idx.checked_sub(first_idx).expect(
+                            "Elements must not be popped by other threads while the parallel map runs.",
+                        );
+                        let chunk_end = begin + chunk.len();
+                        output.assert_has_capacity_for(chunk_end - 1);
+                        if output.vec.capacity() < chunk_end {
+                            output.grow_to(chunk_end);
                         }
-                        break;
-                    }
-                    WritePermit::GrowThenWrite => {
-                        self.grow_to(end_idx);
-                        let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..end_idx) };
-                        for (p, value) in iter.zip(values) {
-                            unsafe { p.write(value) };
+                        for (idx, x) in (begin..chunk_end).zip(chunk) {
+                            let result = f(x);
+                            // SAFETY: positions of the pulled chunk are exclusive to this thread
+                            unsafe { output.ptr(idx).write(result) };
+                            mapped.push(idx);
                         }
-                        break;
                     }
-                    WritePermit::Spin => {}
+                    None if closed => return mapped.take(),
+                    None => std::thread::yield_now(),
                 }
             }
+        };
+
+        let results: Vec<_> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..num_threads).map(|_| s.spawn(map_chunks)).collect();
+            handles.into_iter().map(|h| h.join()).collect()
+        });
+
+        // results of all threads are dropped if any of the threads panicked
+        let mut mapped = MappedRanges::new(&output);
+        let mut panic = None;
+        for result in results {
+            match result {
+                Ok(ranges) => mapped.ranges.extend(ranges),
+                Err(e) => panic = Some(e),
+            }
+        }
+        if let Some(panic) = panic {
+            drop(mapped);
+            std::panic::resume_unwind(panic);
+        }
 
-            while comp_exch_weak(&self.written, begin_idx, end_idx).is_err() {}
+        let mut ranges = mapped.take();
+        drop(mapped);
+        let num_mapped: usize = ranges.iter().map(|x| x.len()).sum();
+        let len = ranges.iter().map(|x| x.end).max().unwrap_or(0);
+
+        match num_mapped == len {
+            true => {
+                let mut output = output;
+                output.set_valid_range(0..len);
+                output.into_vec()
+            }
+            // other threads popped some of the elements, leaving gaps in the output
+            false => {
+                ranges.sort_by_key(|x| x.start);
+                let mut results = Vec::with_capacity(num_mapped);
+                for idx in ranges.into_iter().flatten() {
+                    // SAFETY: each mapped position is written once and it is read once
+                    results.push(unsafe { output.ptr(idx).read() });
+                }
+                results
+            }
         }
     }
 
+    // grow
+
+    /// Pushes the `value` to the back of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::*;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    /// assert_eq!(queue.into_inner(), vec![1, 2, 3]);
+    /// ```
+    pub fn push(&self, value: T) {
+        _ = self.push_returning_idx(value);
+    }
+
+    /// Extends the queue by pushing `values` elements to the back of the queue.
+    ///
+    /// In order to reduce the number of concurrent state updates, `extend` might be preferred over `push` whenever possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    ///
+    /// queue.extend(1..3);
+    /// queue.extend(vec![3, 4, 5, 6]);
+    ///
+    /// assert_eq!(queue.into_inner(), vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn extend<I, Iter>(&self, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        _ = self.extend_returning_range(values);
+    }
+
     // get
 
     /// Returns the number of elements in the queue.
@@ -741,79 +1449,971 @@ where
         self.written.load(Ordering::Relaxed) == self.popped.load(Ordering::Relaxed)
     }
 
-    /// Returns an iterator of references to items in the queue.
+    // snapshot
+
+    /// Returns clones of the elements which are currently in the queue, from the front to the back.
+    ///
+    /// This method can be called with a shared reference while other threads concurrently push to and pop from the queue.
+    /// The snapshot contains the elements which are committed and not yet popped when it is taken; elements pushed or
+    /// popped meanwhile might or might not be included.
+    ///
+    /// Only the elements in the queue when the snapshot starts are protected: pops and pulls claiming any of them
+    /// wait until the snapshot is completed before moving them out, while pushes and pops of the elements pushed
+    /// afterwards never wait for it. Since each snapshot protects only the positions it reads, pops keep progressing
+    /// even when snapshots are taken back to back by multiple threads.
+    ///
+    /// Ranges of up to 8 concurrent readers are tracked, including the readers of the `Debug`, `Clone` and
+    /// `PartialEq` implementations. Further concurrent readers never wait; however, they protect all elements
+    /// and every pop and pull waits for them.
+    /// Still, the snapshot is expected to be used for monitoring purposes rather than on the hot path.
     ///
     /// # Examples
     ///
     /// ```
     /// use orx_concurrent_queue::ConcurrentQueue;
     ///
-    /// let mut queue = ConcurrentQueue::new();
-    ///
-    /// queue.push(1);
-    /// queue.push(2);
-    /// queue.push(3);
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(["a", "b", "c"].map(String::from));
+    /// _ = queue.pop();
     ///
-    /// let sum: i32 = queue.iter().sum();
-    /// assert_eq!(sum, 6);
+    /// assert_eq!(queue.snapshot(), vec!["b", "c"]);
+    /// assert_eq!(queue.len(), 2);
     /// ```
+    pub fn snapshot(&self) -> Vec<T>
+    where
+        T: Clone + Sync,
+    {
+        self.read_pending(|iter| iter.cloned().collect())
+    }
+
+    /// Calls `f` on each element which is currently in the queue, from the front to the back.
     ///
-    /// # Safety
+    /// This method can be called with a shared reference while other threads concurrently push to and pop from the queue;
+    /// see [`snapshot`] for the elements which are visited and how concurrent pops are handled.
     ///
-    /// Notice that this call requires a mutually exclusive `&mut self` reference.
-    /// This is due to the fact that iterators are lazy and they are not necessarily consumed immediately.
-    /// On the other hand, concurrent queue allows for popping elements from the queue with a shared reference.
-    /// This could've led to the following undefined behavior.
+    /// [`snapshot`]: crate::ConcurrentQueue::snapshot
     ///
-    /// To prevent this, `iter` requires a mutually exclusive reference, and hence, the following code does not compile.
+    /// # Deadlocks
     ///
-    /// ```compile_fail
+    /// Pops and pulls claiming any of the elements visited by `f` wait until `f` returns.
+    /// Since the front of the queue is among the visited elements, `f` must not pop or pull from this queue,
+    /// which would wait for itself; pushing to the queue within `f` is safe.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// use orx_concurrent_queue::ConcurrentQueue;
     ///
     /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..10);
+    /// _ = queue.pull(4);
     ///
-    /// queue.push(1);
-    /// queue.push(2);
-    /// queue.push(3);
+    /// let mut sum = 0;
+    /// queue.for_each_pending(|x| sum += x);
+    /// assert_eq!(sum, 4 + 5 + 6 + 7 + 8 + 9);
+    /// ```
+    pub fn for_each_pending<F>(&self, f: F)
+    where
+        T: Sync,
+        F: FnMut(&T),
+    {
+        self.read_pending(|iter| iter.for_each(f))
+    }
+
+    // capacity
+
+    /// Returns the number of positions of the underlying storage which are currently allocated.
     ///
-    /// let iter = queue.iter(); // iterator over elements 1, 2 and 3
+    /// Note that positions of popped elements are not reused; hence, the capacity covers
+    /// all elements ever pushed to the queue, rather than only the elements currently in the queue.
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Returns the maximum number of positions the underlying storage can concurrently grow to.
     ///
-    /// _ = queue.pop(); // 1 is removed
+    /// The queue panics when the total number of elements ever pushed to it exceeds the maximum capacity.
+    /// The maximum capacity can be increased by [`reserve_max_capacity`] with a `&mut self` reference.
     ///
-    /// let sum = iter.sum(); // UB
-    /// ```
-    pub fn iter(&mut self) -> impl ExactSizeIterator<Item = &T> {
-        QueueIterOfRef::<T, P>::new(self.ptr_iter())
+    /// [`reserve_max_capacity`]: crate::ConcurrentQueue::reserve_max_capacity
+    pub fn max_capacity(&self) -> usize {
+        self.vec.max_capacity()
     }
 
-    /// Returns an iterator of mutable references to items in the queue.
+    /// Returns the number of elements which can still be pushed to the queue before it reaches its maximum capacity.
     ///
     /// # Examples
     ///
     /// ```
     /// use orx_concurrent_queue::ConcurrentQueue;
     ///
-    /// let mut queue = ConcurrentQueue::new();
-    ///
-    /// queue.push(1);
-    /// queue.push(2);
-    /// queue.push(3);
+    /// let queue = ConcurrentQueue::with_fixed_capacity(10);
+    /// assert_eq!(queue.remaining_capacity(), 10);
     ///
-    /// for x in queue.iter_mut() {
-    ///     *x += 10;
-    /// }
+    /// queue.extend(0..4);
+    /// _ = queue.pop();
     ///
-    /// assert_eq!(queue.into_inner(), vec![11, 12, 13]);
+    /// // positions of popped elements are not reused
+    /// assert_eq!(queue.remaining_capacity(), 6);
     /// ```
-    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut T> {
-        QueueIterOfMut::<T, P>::new(self.ptr_iter())
-    }
-
-    // helpers
-
-    #[inline(always)]
-    unsafe fn ptr(&self, idx: usize) -> *mut T {
-        unsafe { self.vec.get_ptr_mut(idx) }
+    pub fn remaining_capacity(&self) -> usize {
+        let max_capacity = self.vec.max_capacity();
+        max_capacity.saturating_sub(self.write_reserved.load(Ordering::Relaxed))
+    }
+
+    /// Grows the underlying storage ahead of demand so that at least `additional` more elements can be pushed
+    /// to the queue without any allocation.
+    ///
+    /// Unlike [`reserve_max_capacity`], this method requires only a shared reference and can be called
+    /// concurrently with pushes and pops; however, it cannot grow beyond the [`max_capacity`].
+    ///
+    /// [`reserve_max_capacity`]: crate::ConcurrentQueue::reserve_max_capacity
+    /// [`max_capacity`]: crate::ConcurrentQueue::max_capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics if the required capacity exceeds the maximum capacity of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    /// queue.extend(0..10);
+    ///
+    /// queue.reserve(1000);
+    /// assert!(queue.capacity() >= 1010);
+    ///
+    /// queue.extend(10..1010); // does not allocate
+    /// ```
+    pub fn reserve(&self, additional: usize) {
+        let num_reserved = self.write_reserved.load(Ordering::Relaxed);
+        let new_capacity = num_reserved.saturating_add(additional);
+        if new_capacity > self.vec.capacity() {
+            self.assert_has_capacity_for(new_capacity - 1);
+            self.grow_to(new_capacity);
+        }
+    }
+
+    /// Returns the allocation policy of the queue.
+    pub fn allocation_policy(&self) -> AllocationPolicy {
+        self.allocation_policy
+    }
+
+    /// Sets the allocation policy of the queue.
+    pub fn set_allocation_policy(&mut self, allocation_policy: AllocationPolicy) {
+        self.allocation_policy = allocation_policy;
+    }
+
+    /// Returns the queue with the given allocation policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{AllocationPolicy, ConcurrentQueue};
+    ///
+    /// let queue = ConcurrentQueue::with_linear_growth(4, 8)
+    ///     .with_allocation_policy(AllocationPolicy::Ahead { usage_percent: 50 });
+    ///
+    /// queue.extend(0..8);
+    /// assert_eq!(queue.capacity(), 16);
+    ///
+    /// // 9 reserved positions exceed 50% of the capacity; the next fragment is allocated ahead of demand
+    /// queue.push(8);
+    /// assert_eq!(queue.capacity(), 32);
+    /// ```
+    pub fn with_allocation_policy(mut self, allocation_policy: AllocationPolicy) -> Self {
+        self.allocation_policy = allocation_policy;
+        self
+    }
+
+    /// Returns the wait strategy used by [`pull_batch`].
+    ///
+    /// [`pull_batch`]: crate::ConcurrentQueue::pull_batch
+    pub fn wait_strategy(&self) -> WaitStrategy {
+        self.wait_strategy
+    }
+
+    /// Sets the wait strategy used by [`pull_batch`].
+    ///
+    /// [`pull_batch`]: crate::ConcurrentQueue::pull_batch
+    pub fn set_wait_strategy(&mut self, wait_strategy: WaitStrategy) {
+        self.wait_strategy = wait_strategy;
+    }
+
+    /// Returns the queue with the given wait strategy used by [`pull_batch`].
+    ///
+    /// [`pull_batch`]: crate::ConcurrentQueue::pull_batch
+    pub fn with_wait_strategy(mut self, wait_strategy: WaitStrategy) -> Self {
+        self.wait_strategy = wait_strategy;
+        self
+    }
+
+    /// Increases the maximum capacity of the queue, if necessary, so that at least `additional` more elements
+    /// can be pushed to the queue; and returns the new maximum capacity.
+    ///
+    /// See [`try_reserve_max_capacity`] for a fallible version.
+    ///
+    /// [`try_reserve_max_capacity`]: crate::ConcurrentQueue::try_reserve_max_capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics if the required capacity overflows or the underlying storage fails to reach the required capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::with_linear_growth(4, 2);
+    /// assert_eq!(queue.max_capacity(), 32);
+    ///
+    /// queue.extend(0..30);
+    /// _ = queue.pull(20);
+    /// assert_eq!(queue.remaining_capacity(), 2);
+    ///
+    /// // between the concurrent phases
+    /// queue.reserve_max_capacity(100);
+    /// assert!(queue.remaining_capacity() >= 100);
+    ///
+    /// queue.extend(30..130);
+    /// assert_eq!(queue.len(), 110);
+    /// ```
+    pub fn reserve_max_capacity(&mut self, additional: usize) -> usize
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+    {
+        self.try_reserve_max_capacity(additional)
+            .expect("Failed to reserve the maximum capacity of the concurrent queue")
+    }
+
+    /// Tries to increase the maximum capacity of the queue, if necessary, so that at least `additional` more elements
+    /// can be pushed to the queue:
+    ///
+    /// * returns Ok of the new maximum capacity if it succeeds,
+    /// * returns a [`ReserveMaxCapacityError`] if the required capacity overflows or the underlying storage
+    ///   fails to reach it.
+    ///
+    /// The queue is left unchanged if the maximum capacity is already sufficient or the required capacity
+    /// overflows. Otherwise, the remaining elements are first moved to the front of the storage as in [`compact`],
+    /// and the maximum capacity is increased as much as the underlying storage allows.
+    ///
+    /// [`ReserveMaxCapacityError`]: crate::ReserveMaxCapacityError
+    /// [`compact`]: crate::ConcurrentQueue::compact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{ConcurrentQueue, ReserveMaxCapacityError};
+    ///
+    /// let mut queue = ConcurrentQueue::with_fixed_capacity(4);
+    /// queue.extend(0..4);
+    /// assert_eq!(queue.remaining_capacity(), 0);
+    ///
+    /// assert!(queue.try_reserve_max_capacity(4).is_ok_and(|x| x >= 8));
+    /// queue.extend(4..8);
+    /// assert_eq!(queue.into_inner(), (0..8).collect::<Vec<_>>());
+    ///
+    /// let mut queue = ConcurrentQueue::<u64, _>::with_fixed_capacity(4);
+    /// let error = queue.try_reserve_max_capacity(usize::MAX).unwrap_err();
+    /// assert_eq!(error, ReserveMaxCapacityError::CapacityOverflow { max_capacity: 4 });
+    /// assert_eq!(error.max_capacity(), 4);
+    /// ```
+    pub fn try_reserve_max_capacity(
+        &mut self,
+        additional: usize,
+    ) -> Result<usize, ReserveMaxCapacityError>
+    where
+        <P as ConcurrentPinnedVec<T>>::P:
+            PseudoDefault + IntoConcurrentPinnedVec<T, ConPinnedVec = P>,
+    {
+        let written = self.written.load(Ordering::Relaxed);
+        debug_assert_eq!(written, self.write_reserved.load(Ordering::Relaxed));
+
+        let max_capacity = self.vec.max_capacity();
+        let max_allocation = isize::MAX as usize / core::mem::size_of::<T>().max(1);
+        let required_capacity = match written.checked_add(additional) {
+            Some(x) if x <= max_allocation => x,
+            _ => return Err(ReserveMaxCapacityError::CapacityOverflow { max_capacity }),
+        };
+
+        if required_capacity <= max_capacity {
+            return Ok(max_capacity);
+        }
+
+        // the queue is left empty while the remaining elements are owned by vec, which holds no moved out slots;
+        // hence, a panic in between drops each remaining element at most once
+        let vec = self.take_inner();
+        let len = vec.len();
+        let required_capacity = len + additional;
+
+        // the concurrent vec does not track its written positions; hence, it is never dropped, leaking on a panic
+        let mut vec = core::mem::ManuallyDrop::new(vec.into_concurrent());
+        // SAFETY: with a mut ref, all and only positions 0..len are written;
+        // reserving might reallocate the storage, converting into the pinned vec and back refreshes the pointers
+        let reserved_capacity =
+            unsafe { vec.reserve_maximum_concurrent_capacity(len, required_capacity) };
+        let vec = core::mem::ManuallyDrop::into_inner(vec);
+        self.vec = unsafe { vec.into_inner(len) }.into_concurrent();
+
+        self.written.store(len, Ordering::Relaxed);
+        self.write_reserved.store(len, Ordering::Relaxed);
+
+        let max_capacity = self.vec.max_capacity();
+        match reserved_capacity >= required_capacity && max_capacity >= required_capacity {
+            true => Ok(max_capacity),
+            false => Err(ReserveMaxCapacityError::StorageLimit {
+                max_capacity,
+                required_capacity,
+            }),
+        }
+    }
+
+    /// Returns an iterator of references to items in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// let sum: i32 = queue.iter().sum();
+    /// assert_eq!(sum, 6);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// Notice that this call requires a mutually exclusive `&mut self` reference.
+    /// This is due to the fact that iterators are lazy and they are not necessarily consumed immediately.
+    /// On the other hand, concurrent queue allows for popping elements from the queue with a shared reference.
+    /// This could've led to the following undefined behavior.
+    ///
+    /// To prevent this, `iter` requires a mutually exclusive reference, and hence, the following code does not compile.
+    ///
+    /// ```compile_fail
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let queue = ConcurrentQueue::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// let iter = queue.iter(); // iterator over elements 1, 2 and 3
+    ///
+    /// _ = queue.pop(); // 1 is removed
+    ///
+    /// let sum = iter.sum(); // UB
+    /// ```
+    pub fn iter(&mut self) -> impl ExactSizeIterator<Item = &T> {
+        QueueIterOfRef::<T, P>::new(self.ptr_iter())
+    }
+
+    /// Returns an iterator of mutable references to items in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    ///
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// for x in queue.iter_mut() {
+    ///     *x += 10;
+    /// }
+    ///
+    /// assert_eq!(queue.into_inner(), vec![11, 12, 13]);
+    /// ```
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut T> {
+        QueueIterOfMut::<T, P>::new(self.ptr_iter())
+    }
+
+    // exclusive access
+
+    /// Returns a reference to the element at the given `index` from the front of the queue;
+    /// returns None if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// queue.extend([1, 2, 3, 4]);
+    /// _ = queue.pop();
+    ///
+    /// assert_eq!(queue.get(0), Some(&2));
+    /// assert_eq!(queue.get(2), Some(&4));
+    /// assert_eq!(queue.get(3), None);
+    /// ```
+    pub fn get(&mut self, index: usize) -> Option<&T> {
+        let range = self.valid_range();
+        match index < range.len() {
+            // SAFETY: with a mut ref, positions of the valid range are written
+            true => Some(unsafe { &*self.ptr(range.start + index) }),
+            false => None,
+        }
+    }
+
+    /// Returns a mutable reference to the element at the given `index` from the front of the queue;
+    /// returns None if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// queue.extend([1, 2, 3, 4]);
+    /// _ = queue.pop();
+    ///
+    /// if let Some(x) = queue.get_mut(1) {
+    ///     *x = 42;
+    /// }
+    /// assert_eq!(queue.into_inner(), vec![2, 42, 4]);
+    /// ```
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let range = self.valid_range();
+        match index < range.len() {
+            // SAFETY: with a mut ref, positions of the valid range are written
+            true => Some(unsafe { &mut *self.ptr(range.start + index) }),
+            false => None,
+        }
+    }
+
+    /// Returns a reference to the element at the front of the queue, which would be popped next;
+    /// returns None if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// assert_eq!(queue.front(), None);
+    ///
+    /// queue.extend([1, 2, 3]);
+    /// _ = queue.pop();
+    /// assert_eq!(queue.front(), Some(&2));
+    /// ```
+    pub fn front(&mut self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the element at the back of the queue, which is pushed last;
+    /// returns None if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// assert_eq!(queue.back(), None);
+    ///
+    /// queue.extend([1, 2, 3]);
+    /// assert_eq!(queue.back(), Some(&3));
+    /// ```
+    pub fn back(&mut self) -> Option<&T> {
+        let len = self.valid_range().len();
+        len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Returns an iterator of slices which together contain the elements of the queue in order.
+    ///
+    /// The queue is backed by a pinned vector which might store its elements in multiple fragments.
+    /// Therefore, unlike `VecDeque::as_slices`, the elements might be spread over more than two slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::with_linear_growth(2, 4);
+    /// queue.extend(0..7);
+    /// _ = queue.pull(2);
+    ///
+    /// let slices: Vec<_> = queue.as_slices().collect();
+    /// assert_eq!(slices, vec![&[2, 3][..], &[4, 5, 6][..]]);
+    /// ```
+    pub fn as_slices(&mut self) -> impl Iterator<Item = &[T]> {
+        let range = self.valid_range();
+        self.vec.slices(range).into_iter()
+    }
+
+    /// Returns an iterator of mutable slices which together contain the elements of the queue in order.
+    ///
+    /// See [`as_slices`] for details.
+    ///
+    /// [`as_slices`]: crate::ConcurrentQueue::as_slices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::with_linear_growth(2, 4);
+    /// queue.extend(0..7);
+    ///
+    /// for slice in queue.as_mut_slices() {
+    ///     slice.reverse();
+    /// }
+    /// assert_eq!(queue.into_inner(), vec![3, 2, 1, 0, 6, 5, 4]);
+    /// ```
+    pub fn as_mut_slices(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let range = self.valid_range();
+        // SAFETY: with a mut ref, positions of the valid range are written and exclusively accessed
+        unsafe { self.vec.slices_mut(range) }.into_iter()
+    }
+
+    /// Retains only the elements for which the predicate `f` returns true, dropping the others.
+    ///
+    /// The order of the retained elements is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// queue.extend(0..10);
+    /// _ = queue.pop();
+    ///
+    /// queue.retain(|x| x % 3 == 0);
+    /// assert_eq!(queue.into_inner(), vec![3, 6, 9]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let range = self.valid_range();
+        let mut guard = RetainGuard {
+            popped: range.start,
+            kept: range.start,
+            processed: range.start,
+            end: range.end,
+            queue: self,
+        };
+        guard.queue.set_valid_range(range.start..range.start);
+
+        while guard.processed < guard.end {
+            let idx = guard.processed;
+            // SAFETY: positions from `processed` are written and not yet moved or dropped
+            let ptr = unsafe { guard.queue.ptr(idx) };
+            let keep = f(unsafe { &*ptr });
+            guard.processed += 1;
+            match keep {
+                true => {
+                    if guard.kept != idx {
+                        // SAFETY: the position `kept` is before `idx` and its element is already moved or dropped
+                        unsafe { guard.queue.ptr(guard.kept).write(ptr.read()) };
+                    }
+                    guard.kept += 1;
+                }
+                false => unsafe { ptr.drop_in_place() },
+            }
+        }
+    }
+
+    /// Removes the elements in the `range`, which is relative to the front of the queue, and returns them
+    /// as an iterator.
+    ///
+    /// Elements of the range which are not consumed are dropped together with the iterator.
+    /// Draining a range at the front of the queue is equivalent to pulling it, while draining any other range
+    /// moves the following elements to close the gap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end or if its end is greater than the length of the queue,
+    /// including the bounds which are beyond the maximum usize such as `..=usize::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// queue.extend(0..8);
+    /// _ = queue.pop();
+    ///
+    /// let drained: Vec<_> = queue.drain(2..5).collect();
+    /// assert_eq!(drained, vec![3, 4, 5]);
+    ///
+    /// let drained: Vec<_> = queue.drain(..2).collect();
+    /// assert_eq!(drained, vec![1, 2]);
+    ///
+    /// assert_eq!(queue.into_inner(), vec![6, 7]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> QueueDrain<'_, T, P>
+    where
+        R: RangeBounds<usize>,
+    {
+        let valid = self.valid_range();
+        let len = valid.len();
+        let begin = match range.start_bound() {
+            Bound::Included(&x) => Some(x),
+            Bound::Excluded(&x) => x.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&x) => x.checked_add(1),
+            Bound::Excluded(&x) => Some(x),
+            Bound::Unbounded => Some(len),
+        };
+
+        // bounds which overflow are beyond the maximum usize, and hence, out of bounds
+        let bounds = begin
+            .zip(end)
+            .filter(|&(begin, end)| begin <= end && end <= len);
+        let display = |x: Option<usize>| x.map_or("usize::MAX + 1".to_string(), |x| x.to_string());
+        assert!(
+            bounds.is_some(),
+            "Drain range {}..{} is out of bounds of the queue with length {len}.",
+            display(begin),
+            display(end),
+        );
+        let (begin, end) = bounds.unwrap_or_default();
+        QueueDrain::new(self, (valid.start + begin)..(valid.start + end))
+    }
+
+    /// Drops all elements of the queue.
+    ///
+    /// Allocated capacity of the underlying storage is kept; the queue starts writing again from its beginning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// queue.extend(0..100);
+    /// _ = queue.pull(10);
+    ///
+    /// queue.clear();
+    /// assert!(queue.is_empty());
+    ///
+    /// queue.push(42);
+    /// assert_eq!(queue.into_inner(), vec![42]);
+    /// ```
+    pub fn clear(&mut self) {
+        let range = self.valid_range();
+        self.set_valid_range(0..0);
+        // SAFETY: elements of the range are written and no longer tracked by the queue
+        for ptr in unsafe { self.ptr_iter_over(range) } {
+            unsafe { ptr.drop_in_place() };
+        }
+    }
+
+    /// Shortens the queue to keep only its first `len` elements, dropping the others at the back.
+    ///
+    /// Does nothing if `len` is greater than or equal to the length of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::new();
+    /// queue.extend(0..10);
+    /// _ = queue.pull(2);
+    ///
+    /// queue.truncate(3);
+    /// assert_eq!(queue.into_inner(), vec![2, 3, 4]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        let range = self.valid_range();
+        if len < range.len() {
+            let end = range.start + len;
+            self.set_valid_range(range.start..end);
+            // SAFETY: elements beyond `end` are written and no longer tracked by the queue
+            for ptr in unsafe { self.ptr_iter_over(end..range.end) } {
+                unsafe { ptr.drop_in_place() };
+            }
+        }
+    }
+
+    /// Moves the elements of the queue, if necessary, so that they are stored in a single contiguous slice,
+    /// and returns this slice.
+    ///
+    /// The elements are moved either to the beginning of the fragment holding the front of the queue or to
+    /// an allocated fragment after the back of the queue, provided that the fragment is large enough to hold
+    /// all elements. Returns None if none of the allocated fragments of the underlying storage can hold all
+    /// elements, in which case the queue is left unchanged.
+    ///
+    /// A queue backed by a fixed capacity vector is always made contiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ConcurrentQueue;
+    ///
+    /// let mut queue = ConcurrentQueue::with_linear_growth(2, 4);
+    /// queue.extend(0..6);
+    /// _ = queue.pull(3);
+    /// assert_eq!(queue.as_slices().count(), 2);
+    ///
+    /// assert_eq!(queue.make_contiguous(), Some(&mut [3, 4, 5][..]));
+    /// assert_eq!(queue.as_slices().count(), 1);
+    ///
+    /// queue.extend(6..10);
+    /// assert_eq!(queue.make_contiguous(), None);
+    /// ```
+    pub fn make_contiguous(&mut self) -> Option<&mut [T]> {
+        let range = self.valid_range();
+        let len = range.len();
+        if len == 0 {
+            return Some(&mut []);
+        }
+
+        // fragment boundaries are taken from the layout of the storage; slices out of the valid range are
+        // used only for their lengths and their elements are never accessed
+        let capacity = self.vec.capacity();
+        let fragment_lengths = self.vec.slices(0..capacity).into_iter().map(|x| x.len());
+
+        let mut target = None;
+        let mut begin = 0;
+        for fragment_len in fragment_lengths {
+            let end = begin + fragment_len;
+
+            let holds_front = begin <= range.start && range.start < end;
+            if holds_front && range.end <= end {
+                target = Some(range.start);
+                break;
+            }
+            if (holds_front || begin >= range.end) && end - begin >= len {
+                target = Some(begin);
+                break;
+            }
+            begin = end;
+        }
+
+        let target = target?;
+        if target != range.start {
+            for i in 0..len {
+                // SAFETY: target positions are either before the source or beyond the valid range;
+                // hence, each element is read before its position is overwritten
+                unsafe { self.ptr(target + i).write(self.ptr(range.start + i).read()) };
+            }
+            self.set_valid_range(target..(target + len));
+        }
+
+        // SAFETY: the elements are written to a single fragment
+        unsafe { self.vec.slices_mut(target..(target + len)) }
+            .into_iter()
+            .next()
+    }
+
+    // helpers
+
+    /// Pushes the `value` to the back of the queue and returns its position.
+    pub(crate) fn push_returning_idx(&self, value: T) -> usize {
+        let idx = self.write_reserved.fetch_add(1, Ordering::Relaxed);
+        self.assert_has_capacity_for(idx);
+
+        loop {
+            match WritePermit::for_one(self.vec.capacity(), idx) {
+                WritePermit::JustWrite => {
+                    unsafe { self.ptr(idx).write(value) };
+                    break;
+                }
+                WritePermit::GrowThenWrite => {
+                    self.grow_to(idx + 1);
+                    unsafe { self.ptr(idx).write(value) };
+                    break;
+                }
+                WritePermit::Spin => {}
+            }
+        }
+
+        let num_written = idx + 1;
+        while comp_exch_weak(&self.written, idx, num_written).is_err() {}
+
+        self.grow_ahead(num_written);
+
+        idx
+    }
+
+    /// Extends the queue by pushing `values` elements to the back of the queue and returns the range of their positions.
+    pub(crate) fn extend_returning_range<I, Iter>(&self, values: I) -> Range<usize>
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        let values = values.into_iter();
+        let num_items = values.len();
+
+        match num_items > 0 {
+            true => {
+                let begin_idx = self.write_reserved.fetch_add(num_items, Ordering::Relaxed);
+                let end_idx = begin_idx + num_items;
+                self.assert_has_capacity_for(end_idx - 1);
+                self.write_reserved_range(begin_idx..end_idx, values);
+                begin_idx..end_idx
+            }
+            false => {
+                let idx = self.write_reserved.load(Ordering::Relaxed);
+                idx..idx
+            }
+        }
+    }
+
+    /// Extends the queue by pushing `values` elements to the back of the queue only if the underlying storage
+    /// can hold all of them; otherwise, leaves the queue untouched and returns back the values.
+    ///
+    /// Unlike [`extend`], this method never panics due to capacity; hence, it can be used in destructors.
+    ///
+    /// [`extend`]: crate::ConcurrentQueue::extend
+    pub(crate) fn try_extend<Iter>(&self, values: Iter) -> Result<(), Iter>
+    where
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        let num_items = values.len();
+        let max_capacity = self.vec.max_capacity();
+        let mut begin_idx = self.write_reserved.load(Ordering::Relaxed);
+        loop {
+            match begin_idx
+                .checked_add(num_items)
+                .filter(|end_idx| *end_idx <= max_capacity)
+            {
+                None => return Err(values),
+                Some(end_idx) => match self.write_reserved.compare_exchange_weak(
+                    begin_idx,
+                    end_idx,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => begin_idx = current,
+                },
+            }
+        }
+
+        if num_items > 0 {
+            self.write_reserved_range(begin_idx..(begin_idx + num_items), values);
+        }
+        Ok(())
+    }
+
+    /// Writes the `values` to the reserved and non-empty `range` of positions which are within the maximum capacity,
+    /// and commits them once all positions before the range are committed.
+    fn write_reserved_range<Iter>(&self, range: Range<usize>, values: Iter)
+    where
+        Iter: Iterator<Item = T>,
+    {
+        let (begin_idx, end_idx) = (range.start, range.end);
+        let last_idx = end_idx - 1;
+
+        loop {
+            match WritePermit::for_many(self.vec.capacity(), begin_idx, last_idx) {
+                WritePermit::JustWrite => {
+                    let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..end_idx) };
+                    for (p, value) in iter.zip(values) {
+                        unsafe { p.write(value) };
+                    }
+                    break;
+                }
+                WritePermit::GrowThenWrite => {
+                    self.grow_to(end_idx);
+                    let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..end_idx) };
+                    for (p, value) in iter.zip(values) {
+                        unsafe { p.write(value) };
+                    }
+                    break;
+                }
+                WritePermit::Spin => {}
+            }
+        }
+
+        while comp_exch_weak(&self.written, begin_idx, end_idx).is_err() {}
+
+        self.grow_ahead(end_idx);
+    }
+
+    /// Reserves the range of at most `chunk_size` consecutive elements from the front of the queue to be pulled;
+    /// returns None if `chunk_size` is zero or the queue is empty.
+    fn pull_range(&self, chunk_size: usize) -> Option<Range<usize>> {
+        match chunk_size > 0 {
+            true => {
+                let begin_idx = self.popped.fetch_add(chunk_size, Ordering::SeqCst);
+                let end_idx = begin_idx + chunk_size;
+
+                loop {
+                    let written = self.written.load(Ordering::Acquire);
+
+                    let has_none = begin_idx >= written;
+                    let has_some = !has_none;
+                    let has_all = end_idx <= written;
+
+                    let range = match (has_some, has_all) {
+                        (false, _) => match comp_exch(&self.popped, end_idx, begin_idx).is_ok() {
+                            true => return None,
+                            false => None,
+                        },
+                        (true, true) => Some(begin_idx..end_idx),
+                        (true, false) => Some(begin_idx..written),
+                    };
+
+                    if let Some(range) = range {
+                        let ok = match has_all {
+                            true => true,
+                            false => comp_exch(&self.popped, end_idx, range.end).is_ok(),
+                        };
+
+                        if ok {
+                            self.readers.wait_for_readers_of(range.clone());
+                            return Some(range);
+                        }
+                    }
+                }
+            }
+            false => None,
+        }
+    }
+
+    /// Pulls `min(available, max)` consecutive elements only if at least `min` elements are available;
+    /// returns None otherwise, in which case `popped` is never modified.
+    fn pull_available(&self, min: usize, max: usize) -> Option<QueueIterOwned<'_, T, P>> {
+        loop {
+            let begin_idx = self.popped.load(Ordering::Relaxed);
+            let written = self.written.load(Ordering::Acquire);
+
+            // popped might temporarily exceed written while another thread reverts its pull
+            let num_available = written.saturating_sub(begin_idx);
+            if num_available < min {
+                return None;
+            }
+
+            let end_idx = begin_idx + num_available.min(max);
+            if comp_exch_seq_cst(&self.popped, begin_idx, end_idx).is_ok() {
+                self.readers.wait_for_readers_of(begin_idx..end_idx);
+                let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..end_idx) };
+                return Some(QueueIterOwned::new(iter));
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// Elements in the `range` must be written; the caller is responsible for their ownership.
+    #[inline(always)]
+    pub(crate) unsafe fn ptr_iter_over(&self, range: Range<usize>) -> P::PtrIter<'_> {
+        unsafe { self.vec.ptr_iter_unchecked(range) }
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn ptr(&self, idx: usize) -> *mut T {
+        unsafe { self.vec.get_ptr_mut(idx) }
     }
 
     #[inline(always)]
@@ -824,17 +2424,106 @@ where
         );
     }
 
-    fn grow_to(&self, new_capacity: usize) {
+    /// Grows the underlying storage to at least `new_capacity`; growth is serialized among the
+    /// producers and the explicit reservations.
+    pub(crate) fn grow_to(&self, new_capacity: usize) {
+        let _guard = FlagGuard::lock(&self.growing);
         _ = self
             .vec
             .grow_to(new_capacity)
             .expect("The underlying pinned vector reached its capacity and failed to grow");
     }
 
+    /// Allocates the next fragment if `num_reserved` positions pass the threshold of the allocation policy.
+    ///
+    /// At most one fragment is allocated ahead: once a fragment is allocated ahead of demand, the next one is
+    /// allocated only after the producers start reserving positions within it.
+    ///
+    /// Does nothing if another thread is already growing the storage.
+    #[inline(always)]
+    fn grow_ahead(&self, num_reserved: usize) {
+        let capacity = self.vec.capacity();
+        if let Some(threshold) = self.allocation_policy.threshold(capacity)
+            && num_reserved > threshold
+            && num_reserved > self.grown_ahead_from.load(Ordering::Relaxed)
+            && capacity < self.vec.max_capacity()
+            && let Some(_guard) = FlagGuard::try_lock(&self.growing)
+            && num_reserved > self.grown_ahead_from.load(Ordering::Relaxed)
+        {
+            let capacity = self.vec.capacity();
+            self.grown_ahead_from.store(capacity, Ordering::Relaxed);
+            _ = self.vec.grow_to(capacity + 1);
+        }
+    }
+
+    /// Returns the number of elements written so far, including the popped ones.
+    #[inline(always)]
+    pub(crate) fn num_written(&self) -> usize {
+        self.written.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of elements popped so far.
+    #[inline(always)]
+    pub(crate) fn num_popped(&self) -> usize {
+        self.popped.load(Ordering::Relaxed)
+    }
+
+    /// Calls `f` with an iterator over the committed and not yet popped elements under a shared reference.
+    ///
+    /// Pops concurrently claiming any of these elements wait until `f` returns before moving them out,
+    /// while pops and pulls of the elements pushed after `f` is called never wait.
+    /// Since multiple threads might read the same elements, they are required to be `Sync`.
+    /// Elements pushed or popped while `f` runs might or might not be observed.
+    /// Therefore, `f` must not pop from this queue, which would wait for itself; while reading this or another queue
+    /// within `f` is safe, since registering a reader never waits.
+    pub(crate) fn read_pending<R>(&self, f: impl FnOnce(QueueIterOfRef<'_, T, P>) -> R) -> R
+    where
+        T: Sync,
+    {
+        let guard = self.readers.register();
+        let written = self.written.load(Ordering::Acquire);
+        // popped might temporarily exceed written while another thread reverts its pull
+        let popped = self.popped.load(Ordering::SeqCst).min(written);
+        guard.set_range(popped..written);
+        // SAFETY: elements in popped..written are written, and they cannot be moved out while the guard is alive
+        let iter = unsafe { self.vec.ptr_iter_unchecked(popped..written) };
+        f(QueueIterOfRef::new(iter))
+    }
+
+    /// Creates an empty queue with the same policies and a storage of the same kind as this queue.
+    pub(crate) fn empty_like(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            phantom: PhantomData,
+            written: 0.into(),
+            write_reserved: 0.into(),
+            popped: 0.into(),
+            chunk_policy: self.chunk_policy,
+            pull_counters: PullCounters::new(self.pull_counters.is_enabled()),
+            allocation_policy: self.allocation_policy,
+            wait_strategy: self.wait_strategy,
+            growing: false.into(),
+            grown_ahead_from: 0.into(),
+            readers: Readers::default(),
+            // SAFETY: all positions 0..0 are written
+            vec: unsafe { self.vec.clone_with_len(0) },
+        }
+    }
+
     pub(super) fn valid_range(&mut self) -> Range<usize> {
         self.popped.load(Ordering::Relaxed)..self.written.load(Ordering::Relaxed)
     }
 
+    /// Sets the valid range of the queue; elements in the `range` must be written while those out of it are
+    /// already dropped or moved.
+    pub(super) fn set_valid_range(&mut self, range: Range<usize>) {
+        self.popped.store(range.start, Ordering::Relaxed);
+        self.write_reserved.store(range.end, Ordering::Relaxed);
+        self.written.store(range.end, Ordering::Relaxed);
+    }
+
     pub(super) fn ptr_iter(&mut self) -> P::PtrIter<'_> {
         let range = self.valid_range();
         // SAFETY: with a mut ref, we ensure that the range contains all and only valid values
@@ -874,3 +2563,79 @@ where
         (vec, written, popped)
     }
 }
+
+/// Restores the queue in case the predicate of `retain` panics by moving the unprocessed elements next to the
+/// retained ones; the valid range of the queue is updated once retain completes or unwinds.
+struct RetainGuard<'a, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: &'a mut ConcurrentQueue<T, P>,
+    popped: usize,
+    kept: usize,
+    processed: usize,
+    end: usize,
+}
+
+impl<T, P> Drop for RetainGuard<'_, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn drop(&mut self) {
+        let num_unprocessed = self.end - self.processed;
+        if self.kept != self.processed {
+            for i in 0..num_unprocessed {
+                // SAFETY: unprocessed elements are moved to positions which are already moved or dropped
+                unsafe {
+                    let src = self.queue.ptr(self.processed + i);
+                    self.queue.ptr(self.kept + i).write(src.read());
+                }
+            }
+        }
+        self.queue
+            .set_valid_range(self.popped..(self.kept + num_unprocessed));
+    }
+}
+
+/// Positions of the output of a parallel map which are written by a thread, the results of which are
+/// dropped unless they are taken out.
+#[cfg(feature = "std")]
+struct MappedRanges<'a, R: Send> {
+    output: &'a ConcurrentQueue<R>,
+    ranges: Vec<Range<usize>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, R: Send> MappedRanges<'a, R> {
+    fn new(output: &'a ConcurrentQueue<R>) -> Self {
+        Self {
+            output,
+            ranges: Vec::new(),
+        }
+    }
+
+    /// Records that the position `idx` of the output is written.
+    fn push(&mut self, idx: usize) {
+        match self.ranges.last_mut() {
+            Some(range) if range.end == idx => range.end += 1,
+            _ => self.ranges.push(idx..(idx + 1)),
+        }
+    }
+
+    /// Takes out the written ranges, which are then no longer dropped by this instance.
+    fn take(&mut self) -> Vec<Range<usize>> {
+        core::mem::take(&mut self.ranges)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Send> Drop for MappedRanges<'_, R> {
+    fn drop(&mut self) {
+        for idx in self.ranges.iter().cloned().flatten() {
+            // SAFETY: recorded positions are written and not taken out
+            unsafe { self.output.ptr(idx).drop_in_place() };
+        }
+    }
+}
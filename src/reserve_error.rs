@@ -0,0 +1,58 @@
+use core::fmt::{Display, Formatter};
+
+/// Error returned by [`ConcurrentQueue::try_reserve_max_capacity`] when the maximum capacity of the queue
+/// cannot be increased to the required capacity.
+///
+/// The maximum capacity of the queue after the failed attempt is available via [`max_capacity`].
+///
+/// [`ConcurrentQueue::try_reserve_max_capacity`]: crate::ConcurrentQueue::try_reserve_max_capacity
+/// [`max_capacity`]: crate::ReserveMaxCapacityError::max_capacity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReserveMaxCapacityError {
+    /// The required capacity, number of written elements plus the additional elements, exceeds the maximum
+    /// number of elements of the element type that can be allocated; the queue is left unchanged.
+    CapacityOverflow {
+        /// Unchanged maximum capacity of the queue.
+        max_capacity: usize,
+    },
+    /// The underlying storage failed to reach the required capacity.
+    ///
+    /// The remaining elements are moved to the front of the storage, and the maximum capacity might be
+    /// partially increased.
+    StorageLimit {
+        /// Maximum capacity of the queue after the attempt, which might be greater than before the attempt.
+        max_capacity: usize,
+        /// Capacity that is required to push the additional elements.
+        required_capacity: usize,
+    },
+}
+
+impl ReserveMaxCapacityError {
+    /// Returns the maximum capacity of the queue after the failed attempt.
+    pub fn max_capacity(&self) -> usize {
+        match self {
+            Self::CapacityOverflow { max_capacity } => *max_capacity,
+            Self::StorageLimit { max_capacity, .. } => *max_capacity,
+        }
+    }
+}
+
+impl Display for ReserveMaxCapacityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CapacityOverflow { max_capacity } => write!(
+                f,
+                "required capacity overflows; maximum capacity of the queue remains {max_capacity}"
+            ),
+            Self::StorageLimit {
+                max_capacity,
+                required_capacity,
+            } => write!(
+                f,
+                "storage failed to reach the required capacity {required_capacity}; maximum capacity of the queue is {max_capacity}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ReserveMaxCapacityError {}
@@ -0,0 +1,399 @@
+use crate::{ConcurrentQueue, DefaultConPinnedVec, common_traits::iter::QueueIterOwned};
+use alloc::vec::Vec;
+use core::ops::Deref;
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// A concurrent queue composed of multiple independent lanes, each of which is a [`ConcurrentQueue`].
+///
+/// Every lane has its own `written`, `write_reserved` and `popped` counters.
+/// Distributing producers and consumers over the lanes reduces the contention on these shared counters,
+/// which otherwise becomes the scalability ceiling on machines with a high number of cores.
+///
+/// In return, the global FIFO order is relaxed to per-lane FIFO order:
+/// * elements pushed to the same lane are popped in the order they are pushed,
+/// * there is no ordering guarantee among elements of different lanes.
+///
+/// Lanes are selected as follows:
+/// * [`push`] and [`extend`] push to the home lane of the calling thread, while [`push_to`] and [`extend_to`] push to the lane of the given hint;
+/// * [`pop_from`] and [`pull_from`] start from the lane of the given hint, the home lane, and scan the other lanes only if the home lane is empty;
+///   [`pop`] and [`pull`] start from the home lane of the calling thread.
+///
+/// The home lane of a thread is derived from a per-thread hint without touching any shared state:
+/// threads are assigned consecutive hints the first time they access a sharded queue.
+/// Since this requires thread locals, [`push`], [`extend`], [`pop`] and [`pull`] are available only with the
+/// `std` feature. Without it, the lane hints must be provided explicitly.
+///
+/// Using the thread index as the lane hint is a common and effective choice when the threads are known.
+///
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+/// [`push`]: crate::ShardedConcurrentQueue::push
+/// [`extend`]: crate::ShardedConcurrentQueue::extend
+/// [`push_to`]: crate::ShardedConcurrentQueue::push_to
+/// [`extend_to`]: crate::ShardedConcurrentQueue::extend_to
+/// [`pop`]: crate::ShardedConcurrentQueue::pop
+/// [`pull`]: crate::ShardedConcurrentQueue::pull
+/// [`pop_from`]: crate::ShardedConcurrentQueue::pop_from
+/// [`pull_from`]: crate::ShardedConcurrentQueue::pull_from
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::ShardedConcurrentQueue;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let num_threads = 4;
+/// let queue = ShardedConcurrentQueue::new(num_threads);
+///
+/// for i in 0..1000 {
+///     queue.push_to(i, i);
+/// }
+/// assert_eq!(queue.len(), 1000);
+///
+/// let sum = AtomicUsize::new(0);
+///
+/// std::thread::scope(|s| {
+///     for t in 0..num_threads {
+///         let queue = &queue;
+///         let sum = &sum;
+///         s.spawn(move || {
+///             // thread t is at home in lane t
+///             while let Some(x) = queue.pop_from(t) {
+///                 _ = sum.fetch_add(x, Ordering::Relaxed);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert!(queue.is_empty());
+/// assert_eq!(sum.into_inner(), 999 * 1000 / 2);
+/// ```
+pub struct ShardedConcurrentQueue<T, P = DefaultConPinnedVec<T>>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    lanes: Vec<Lane<T, P>>,
+}
+
+impl<T> ShardedConcurrentQueue<T, DefaultConPinnedVec<T>>
+where
+    T: Send,
+{
+    /// Creates a new empty sharded concurrent queue with `num_lanes` lanes.
+    ///
+    /// Each lane is backed with default concurrent pinned vec, which is the concurrent version of [`SplitVec`] with [`Doubling`] growth.
+    ///
+    /// In order to create lanes backed with a particular [`PinnedVec`], you may use [`from_lanes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_lanes` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue: ShardedConcurrentQueue<usize> = ShardedConcurrentQueue::new(8);
+    /// assert_eq!(queue.num_lanes(), 8);
+    /// ```
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Doubling`]: orx_split_vec::Doubling
+    /// [`PinnedVec`]: orx_pinned_vec::PinnedVec
+    /// [`from_lanes`]: crate::ShardedConcurrentQueue::from_lanes
+    pub fn new(num_lanes: usize) -> Self {
+        Self::from_lanes((0..num_lanes).map(|_| ConcurrentQueue::new()))
+    }
+}
+
+impl<T, P> ShardedConcurrentQueue<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Creates a sharded concurrent queue using the given `lanes`.
+    ///
+    /// Lanes might be empty or might already contain elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lanes` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::{ConcurrentQueue, ShardedConcurrentQueue};
+    ///
+    /// let queue = ShardedConcurrentQueue::from_lanes(
+    ///     (0..4).map(|_| ConcurrentQueue::with_linear_growth(10, 64)),
+    /// );
+    /// queue.push_to(0, 'a');
+    /// assert_eq!(queue.num_lanes(), 4);
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn from_lanes(lanes: impl IntoIterator<Item = ConcurrentQueue<T, P>>) -> Self {
+        let lanes: Vec<_> = lanes.into_iter().map(Lane).collect();
+        assert!(
+            !lanes.is_empty(),
+            "Sharded concurrent queue must have at least one lane."
+        );
+        Self { lanes }
+    }
+
+    /// Returns the number of lanes of the queue.
+    pub fn num_lanes(&self) -> usize {
+        self.lanes.len()
+    }
+
+    /// Returns the lane corresponding to the given `lane_hint`, which is the lane at position `lane_hint % num_lanes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(2);
+    /// queue.push_to(3, 'x');
+    ///
+    /// assert_eq!(queue.lane(1).len(), 1);
+    /// assert_eq!(queue.lane(5).pop(), Some('x'));
+    /// ```
+    pub fn lane(&self, lane_hint: usize) -> &ConcurrentQueue<T, P> {
+        &self.lanes[lane_hint % self.lanes.len()]
+    }
+
+    /// Returns an iterator over the lanes of the queue.
+    pub fn lanes(&self) -> impl ExactSizeIterator<Item = &ConcurrentQueue<T, P>> {
+        self.lanes.iter().map(|x| &x.0)
+    }
+
+    /// Consumes the sharded queue and returns its lanes.
+    pub fn into_lanes(self) -> Vec<ConcurrentQueue<T, P>> {
+        self.lanes.into_iter().map(|x| x.0).collect()
+    }
+
+    // shrink
+
+    /// Pops and returns an element from the queue, starting the search from the home lane of the calling thread;
+    /// returns None if all lanes are empty.
+    ///
+    /// Use [`pop_from`] in order to pick the home lane explicitly.
+    ///
+    /// [`pop_from`]: crate::ShardedConcurrentQueue::pop_from
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(3);
+    /// queue.push_to(2, 'a');
+    /// queue.push_to(1, 'b');
+    ///
+    /// let mut popped = vec![queue.pop(), queue.pop()];
+    /// popped.sort();
+    /// assert_eq!(popped, vec![Some('a'), Some('b')]);
+    /// assert_eq!(queue.pop(), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn pop(&self) -> Option<T> {
+        self.pop_from(thread_lane_hint())
+    }
+
+    /// Pops and returns an element from the home lane `lane_hint % num_lanes`;
+    /// if the home lane is empty, the remaining lanes are scanned in order.
+    /// Returns None if all lanes are empty.
+    ///
+    /// Elements of the same lane are popped in FIFO order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(3);
+    /// queue.extend_to(0, [1, 2]);
+    /// queue.extend_to(1, [3, 4]);
+    ///
+    /// assert_eq!(queue.pop_from(1), Some(3));
+    /// assert_eq!(queue.pop_from(1), Some(4));
+    /// assert_eq!(queue.pop_from(1), Some(1)); // scans lane 2, then lane 0
+    /// assert_eq!(queue.pop_from(2), Some(2));
+    /// assert_eq!(queue.pop_from(2), None);
+    /// ```
+    pub fn pop_from(&self, lane_hint: usize) -> Option<T> {
+        self.lanes_from(lane_hint).find_map(|x| x.pop())
+    }
+
+    /// Pulls at most `chunk_size` consecutive elements of a single lane, starting the search from the home lane of
+    /// the calling thread; returns None if `chunk_size` is zero or if all lanes are empty.
+    ///
+    /// Use [`pull_from`] in order to pick the home lane explicitly.
+    ///
+    /// [`pull_from`]: crate::ShardedConcurrentQueue::pull_from
+    #[cfg(feature = "std")]
+    pub fn pull(&self, chunk_size: usize) -> Option<QueueIterOwned<'_, T, P>> {
+        self.pull_from(thread_lane_hint(), chunk_size)
+    }
+
+    /// Pulls at most `chunk_size` consecutive elements from the home lane `lane_hint % num_lanes`;
+    /// if the home lane is empty, the remaining lanes are scanned in order.
+    ///
+    /// Returns None if `chunk_size` is zero or if all lanes are empty.
+    /// Otherwise, the returned non-empty chunk is pulled from a single lane, see [`ConcurrentQueue::pull`].
+    ///
+    /// [`ConcurrentQueue::pull`]: crate::ConcurrentQueue::pull
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(2);
+    /// queue.extend_to(0, 0..5);
+    /// queue.extend_to(1, 5..7);
+    ///
+    /// assert_eq!(queue.pull_from(1, 4).map(|x| x.collect::<Vec<_>>()), Some(vec![5, 6]));
+    /// assert_eq!(queue.pull_from(1, 4).map(|x| x.collect::<Vec<_>>()), Some(vec![0, 1, 2, 3]));
+    /// assert_eq!(queue.pull_from(1, 4).map(|x| x.collect::<Vec<_>>()), Some(vec![4]));
+    /// assert_eq!(queue.pull_from(1, 4).map(|x| x.collect::<Vec<_>>()), None);
+    /// ```
+    pub fn pull_from(
+        &self,
+        lane_hint: usize,
+        chunk_size: usize,
+    ) -> Option<QueueIterOwned<'_, T, P>> {
+        self.lanes_from(lane_hint).find_map(|x| x.pull(chunk_size))
+    }
+
+    // grow
+
+    /// Pushes the `value` to the back of the home lane of the calling thread.
+    ///
+    /// Since a thread keeps its home lane, elements pushed by the same thread are popped in the order they are pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(2);
+    /// queue.push(1);
+    /// queue.push(2);
+    /// queue.push(3);
+    ///
+    /// assert_eq!(queue.len(), 3);
+    /// assert_eq!(queue.pop(), Some(1));
+    /// assert_eq!(queue.pop(), Some(2));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn push(&self, value: T) {
+        self.lane(thread_lane_hint()).push(value)
+    }
+
+    /// Pushes the `value` to the back of the lane `lane_hint % num_lanes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(2);
+    /// queue.push_to(0, 1);
+    /// queue.push_to(2, 2);
+    ///
+    /// assert_eq!(queue.lane(0).len(), 2);
+    /// assert_eq!(queue.lane(1).len(), 0);
+    /// ```
+    pub fn push_to(&self, lane_hint: usize, value: T) {
+        self.lane(lane_hint).push(value)
+    }
+
+    /// Extends the home lane of the calling thread by pushing `values` to its back.
+    ///
+    /// All `values` are pushed to the same lane and they are popped in the order they are pushed.
+    #[cfg(feature = "std")]
+    pub fn extend<I, Iter>(&self, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        self.lane(thread_lane_hint()).extend(values)
+    }
+
+    /// Extends the lane `lane_hint % num_lanes` by pushing `values` to its back.
+    pub fn extend_to<I, Iter>(&self, lane_hint: usize, values: I)
+    where
+        I: IntoIterator<Item = T, IntoIter = Iter>,
+        Iter: ExactSizeIterator<Item = T>,
+    {
+        self.lane(lane_hint).extend(values)
+    }
+
+    // get
+
+    /// Returns the total number of elements in all lanes of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::ShardedConcurrentQueue;
+    ///
+    /// let queue = ShardedConcurrentQueue::new(4);
+    /// queue.extend_to(0, 0..3);
+    /// queue.extend_to(1, 3..5);
+    /// assert_eq!(queue.len(), 5);
+    ///
+    /// _ = queue.pop_from(3);
+    /// assert_eq!(queue.len(), 4);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.lanes().map(|x| x.len()).sum()
+    }
+
+    /// Returns true if all lanes of the queue are empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.lanes().all(|x| x.is_empty())
+    }
+
+    // helpers
+
+    fn lanes_from(&self, lane_hint: usize) -> impl Iterator<Item = &ConcurrentQueue<T, P>> {
+        let n = self.lanes.len();
+        let home = lane_hint % n;
+        (0..n).map(move |i| &self.lanes[(home + i) % n].0)
+    }
+}
+
+/// Returns the lane hint of the calling thread, which is assigned once per thread.
+#[cfg(feature = "std")]
+fn thread_lane_hint() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_HINT: AtomicUsize = AtomicUsize::new(0);
+    std::thread_local! {
+        static HINT: usize = NEXT_HINT.fetch_add(1, Ordering::Relaxed);
+    }
+    HINT.with(|x| *x)
+}
+
+/// A lane of the sharded queue aligned to a separate cache line in order to avoid false sharing
+/// between the atomic counters of the neighboring lanes.
+#[repr(align(128))]
+struct Lane<T, P>(ConcurrentQueue<T, P>)
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>;
+
+impl<T, P> Deref for Lane<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    type Target = ConcurrentQueue<T, P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{AllocationPolicy, ConcurrentQueue};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS * 2), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, 2048)],
+    [|x| x, |x| x.to_string()],
+    [AllocationPolicy::OnDemand, AllocationPolicy::Ahead { usage_percent: 0 }, AllocationPolicy::Ahead { usage_percent: 75 }]
+)]
+fn push_extend_reserve<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, policy: AllocationPolicy)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    let queue = ConcurrentQueue::from(vec).with_allocation_policy(policy);
+    let q = &queue;
+    let f = &f;
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    match i % 3 {
+                        0 => q.push(f(t * 2 * N + i)),
+                        1 => q.extend([f(t * 2 * N + i), f((t * 2 + 1) * N + i)]),
+                        _ => q.reserve(7),
+                    }
+                }
+            });
+        }
+    });
+
+    let mut expected: Vec<_> = (0..NUM_PUSHERS)
+        .flat_map(|t| (0..N).filter(|i| i % 3 != 2).map(move |i| (t, i)))
+        .flat_map(|(t, i)| match i % 3 {
+            0 => alloc::vec![f(t * 2 * N + i)],
+            _ => alloc::vec![f(t * 2 * N + i), f((t * 2 + 1) * N + i)],
+        })
+        .collect();
+    expected.sort();
+
+    assert!(queue.capacity() >= expected.len());
+    let mut collected: Vec<_> = queue.into_inner().into_iter().collect();
+    collected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn allocate_ahead() {
+    let mut queue = ConcurrentQueue::<usize, _>::with_linear_growth(4, 4);
+    assert_eq!(queue.allocation_policy(), AllocationPolicy::OnDemand);
+    queue.extend(0..8);
+    assert_eq!(queue.capacity(), 16);
+
+    queue.set_allocation_policy(AllocationPolicy::Ahead { usage_percent: 50 });
+    queue.push(8);
+    assert_eq!(queue.capacity(), 32);
+    queue.extend(9..16);
+    assert_eq!(queue.capacity(), 32);
+    queue.push(16);
+    assert_eq!(queue.capacity(), 48);
+
+    // cannot grow beyond the max capacity
+    queue.extend(17..64);
+    assert_eq!(queue.capacity(), 64);
+    assert_eq!(queue.remaining_capacity(), 0);
+    assert_eq!(queue.len(), 64);
+}
+
+#[test]
+fn allocate_at_most_one_fragment_ahead() {
+    let queue = ConcurrentQueue::<usize, _>::with_linear_growth(4, 64)
+        .with_allocation_policy(AllocationPolicy::Ahead { usage_percent: 0 });
+    assert_eq!(queue.capacity(), 16);
+
+    for i in 0..100 {
+        queue.push(i);
+        let num_fragments_in_use = (i + 1).div_ceil(16);
+        assert_eq!(queue.capacity(), (num_fragments_in_use + 1) * 16);
+    }
+
+    let queue = ConcurrentQueue::<usize, _>::with_doubling_growth()
+        .with_allocation_policy(AllocationPolicy::Ahead { usage_percent: 0 });
+    let mut capacity_in_use = 4;
+    for i in 0..1000 {
+        queue.push(i);
+        if i == capacity_in_use {
+            capacity_in_use = capacity_in_use * 2 + 4;
+        }
+        assert_eq!(queue.capacity(), capacity_in_use * 2 + 4);
+    }
+}
+
+#[test]
+fn reserve() {
+    let queue = ConcurrentQueue::<usize, _>::with_linear_growth(4, 4);
+    queue.reserve(0);
+    assert_eq!(queue.capacity(), 16);
+    queue.reserve(17);
+    assert_eq!(queue.capacity(), 32);
+
+    queue.extend(0..20);
+    queue.reserve(12);
+    assert_eq!(queue.capacity(), 32);
+    queue.reserve(13);
+    assert_eq!(queue.capacity(), 48);
+}
+
+#[test]
+#[should_panic]
+fn reserve_beyond_max_capacity() {
+    let queue = ConcurrentQueue::<usize, _>::with_linear_growth(4, 4);
+    queue.reserve(65);
+}
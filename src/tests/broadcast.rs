@@ -0,0 +1,178 @@
+use crate::BroadcastQueue;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use std::{
+    fmt::Debug,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS: usize = 2;
+const NUM_SUBSCRIBERS: usize = 3;
+
+#[test_matrix(
+    [1, 7, 1024],
+    [|x| x, |x| x.to_string()]
+)]
+fn broadcast_every_subscriber_receives_all<T>(
+    fragment_capacity: usize,
+    f: impl Fn(usize) -> T + Sync,
+) where
+    T: Send + Sync + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue = BroadcastQueue::with_fragment_capacity(fragment_capacity);
+    let q = &queue;
+    let subscribers: Vec<_> = (0..NUM_SUBSCRIBERS).map(|_| queue.subscribe()).collect();
+    let num_completed_pushers = AtomicUsize::new(0);
+
+    let received: Vec<Vec<T>> = std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS {
+            let num_completed_pushers = &num_completed_pushers;
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+                num_completed_pushers.fetch_add(1, Ordering::Release);
+            });
+        }
+
+        let handles: Vec<_> = subscribers
+            .into_iter()
+            .map(|mut subscriber| {
+                let num_completed_pushers = &num_completed_pushers;
+                s.spawn(move || {
+                    let mut received = Vec::new();
+                    loop {
+                        let done = num_completed_pushers.load(Ordering::Acquire) == NUM_PUSHERS;
+                        while let Some(x) = subscriber.next() {
+                            received.push(x.clone());
+                        }
+                        if done {
+                            break received;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|x| x.join().expect("subscriber must not panic"))
+            .collect()
+    });
+
+    let mut expected: Vec<_> = (0..N * NUM_PUSHERS).map(f).collect();
+    expected.sort();
+
+    for mut received in received {
+        received.sort();
+        assert_eq!(received, expected);
+    }
+
+    // all subscribers are dropped
+    _ = queue.reclaim();
+    assert_eq!(queue.num_retained(), 0);
+}
+
+#[test]
+fn broadcast_reclaims_passed_elements() {
+    struct Flagged<'a>(&'a AtomicBool);
+    impl Drop for Flagged<'_> {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let flags: Vec<_> = (0..10).map(|_| AtomicBool::new(false)).collect();
+    let queue = BroadcastQueue::new();
+
+    let mut s1 = queue.subscribe();
+    let mut s2 = queue.subscribe();
+    queue.extend(flags.iter().map(Flagged));
+
+    for _ in 0..5 {
+        assert!(s1.next().is_some());
+    }
+    for _ in 0..2 {
+        assert!(s2.next().is_some());
+    }
+
+    assert_eq!(queue.reclaim(), 1);
+    let dropped = |x: &[AtomicBool]| x.iter().filter(|x| x.load(Ordering::Relaxed)).count();
+    assert_eq!(dropped(&flags), 1);
+
+    drop(s2);
+    assert_eq!(queue.num_subscribers(), 1);
+    assert_eq!(queue.reclaim(), 3);
+    assert_eq!(dropped(&flags), 4);
+
+    while s1.next().is_some() {}
+    assert_eq!(queue.reclaim(), 6);
+    assert_eq!(dropped(&flags), 10);
+    assert_eq!(queue.num_retained(), 0);
+}
+
+#[test]
+fn broadcast_drops_remaining_elements() {
+    let queue = BroadcastQueue::new();
+    let mut subscriber = queue.subscribe();
+    queue.extend((0..100).map(|x| x.to_string()));
+
+    assert_eq!(subscriber.next(), Some(&0.to_string()));
+    assert_eq!(subscriber.num_pending(), 99);
+}
+
+#[test]
+fn broadcast_frees_passed_fragments() {
+    let queue = BroadcastQueue::with_fragment_capacity(16);
+    let mut s1 = queue.subscribe();
+    let mut s2 = queue.subscribe();
+
+    for i in 0..N {
+        queue.push(i.to_string());
+        assert_eq!(s1.next(), Some(&i.to_string()));
+        if i % 2 == 1 {
+            assert!(s2.next().is_some());
+            assert!(s2.next().is_some());
+        }
+        // passed fragments are freed periodically, every 1024 elements
+        assert!(queue.capacity() <= 1024 + 3 * 16);
+    }
+
+    while s2.next().is_some() {}
+    _ = s1.next();
+    _ = queue.reclaim();
+    assert_eq!(queue.num_retained(), 0);
+    assert!(queue.capacity() <= 16);
+}
+
+#[test]
+fn broadcast_frees_fragments_while_subscriber_lags_behind() {
+    let queue = BroadcastQueue::with_fragment_capacity(4);
+    let mut fast = queue.subscribe();
+    let mut slow = queue.subscribe();
+
+    queue.extend(0..40);
+    while fast.next().is_some() {}
+    for _ in 0..10 {
+        _ = slow.next();
+    }
+
+    // slow subscriber holds the reference of the element at position 9
+    assert_eq!(queue.reclaim(), 9);
+    assert_eq!(queue.capacity(), 40 - 8);
+
+    drop(slow);
+    assert_eq!(queue.reclaim(), 31);
+    assert_eq!(queue.capacity(), 4);
+
+    queue.extend(40..50);
+    let received: Vec<_> = core::iter::from_fn(|| fast.next().copied()).collect();
+    assert_eq!(received, (40..50).collect::<Vec<_>>());
+}
@@ -0,0 +1,123 @@
+use crate::{ChunkPolicy, ConcurrentQueue, PullMetrics, WaitStrategy};
+use alloc::vec::Vec;
+use orx_pinned_vec::ConcurrentPinnedVec;
+use test_case::test_case;
+
+#[test_case(0, 4)]
+#[test_case(4, 4)]
+#[test_case(5, 12)]
+#[test_case(1000, 1020)]
+fn builder_doubling_max_capacity(max_capacity: usize, expected: usize) {
+    let queue = ConcurrentQueue::<usize>::builder()
+        .max_capacity(max_capacity)
+        .build();
+    let (vec, _, _) = unsafe { queue.destruct() };
+    assert_eq!(vec.max_capacity(), expected);
+}
+
+#[test_case(1, 100, 2, 100)]
+#[test_case(64, 1000, 64, 1024)]
+#[test_case(100, 1000, 128, 1024)]
+#[test_case(1024, 1, 1024, 1024)]
+fn builder_linear_max_capacity(
+    fragment_capacity: usize,
+    max_capacity: usize,
+    expected_fragment_capacity: usize,
+    expected: usize,
+) {
+    let queue = ConcurrentQueue::<usize>::builder()
+        .linear_growth(fragment_capacity)
+        .max_capacity(max_capacity)
+        .build();
+    let (vec, _, _) = unsafe { queue.destruct() };
+    assert_eq!(vec.max_capacity(), expected);
+    assert_eq!(vec.max_capacity() % expected_fragment_capacity, 0);
+}
+
+#[test]
+fn builder_fixed_capacity() {
+    let queue = ConcurrentQueue::builder().fixed_capacity(10).build();
+    queue.extend(0..10);
+    assert_eq!(queue.into_inner(), (0..10).collect::<Vec<_>>());
+
+    // setting the same maximum capacity or a maximum capacity before the backend is allowed
+    let queue = ConcurrentQueue::<char>::builder()
+        .max_capacity(20)
+        .fixed_capacity(10)
+        .max_capacity(10)
+        .build();
+    let (vec, _, _) = unsafe { queue.destruct() };
+    assert_eq!(vec.max_capacity(), 10);
+}
+
+#[test]
+#[should_panic]
+fn builder_fixed_capacity_with_different_max_capacity() {
+    let _ = ConcurrentQueue::<char>::builder()
+        .fixed_capacity(10)
+        .max_capacity(20)
+        .build();
+}
+
+#[test]
+#[should_panic]
+fn builder_fixed_capacity_exceeded() {
+    let queue = ConcurrentQueue::builder().fixed_capacity(10).build();
+    queue.extend(0..11);
+}
+
+#[test]
+fn builder_initial_capacity() {
+    let queue = ConcurrentQueue::<usize>::builder()
+        .initial_capacity(100)
+        .build();
+    let (vec, _, _) = unsafe { queue.destruct() };
+    assert!(vec.capacity() >= 100);
+
+    let queue = ConcurrentQueue::<usize>::builder()
+        .linear_growth(16)
+        .initial_capacity(100)
+        .build();
+    let (vec, _, _) = unsafe { queue.destruct() };
+    assert_eq!(vec.capacity(), 112);
+}
+
+#[test]
+#[should_panic]
+fn builder_initial_capacity_exceeds_max_capacity() {
+    let _ = ConcurrentQueue::<usize>::builder()
+        .initial_capacity(100)
+        .max_capacity(50)
+        .build();
+}
+
+#[test]
+fn builder_pull_options() {
+    let queue = ConcurrentQueue::builder()
+        .chunk_policy(ChunkPolicy::Fixed(3))
+        .pull_metrics(false)
+        .build();
+    assert_eq!(queue.chunk_policy(), ChunkPolicy::Fixed(3));
+
+    queue.extend(0..10);
+    while queue.pull_adaptive().is_some() {}
+    assert_eq!(queue.pull_metrics(), PullMetrics::default());
+
+    let queue = ConcurrentQueue::builder().build();
+    assert_eq!(queue.chunk_policy(), ChunkPolicy::default());
+    queue.extend(0..10);
+    while queue.pull_adaptive().is_some() {}
+    assert_eq!(queue.pull_metrics().num_pulled, 10);
+}
+
+#[test]
+fn builder_wait_strategy() {
+    let queue = ConcurrentQueue::<char>::builder().build();
+    assert_eq!(queue.wait_strategy(), WaitStrategy::default());
+
+    let queue = ConcurrentQueue::<char>::builder()
+        .wait_strategy(WaitStrategy::Yield)
+        .fixed_capacity(16)
+        .build();
+    assert_eq!(queue.wait_strategy(), WaitStrategy::Yield);
+}
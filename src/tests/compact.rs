@@ -0,0 +1,108 @@
+use crate::queue::ConcurrentQueue;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::sync::atomic::Ordering;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::{SplitVec, prelude::PseudoDefault};
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 1735;
+
+const NUM_PUSHERS_POPPERS: usize = 4;
+const NUM_ROUNDS: usize = 3;
+
+#[test_matrix(
+    [FixedVec::new(NUM_ROUNDS * N * NUM_PUSHERS_POPPERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(6, NUM_ROUNDS * N * NUM_PUSHERS_POPPERS / 64 + 1)],
+    [|x| x, |x| x.to_string()],
+    [0, N / 2, N]
+)]
+fn compact_between_rounds<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let mut queue = ConcurrentQueue::from(vec);
+    let mut expected = Vec::new();
+
+    for round in 0..NUM_ROUNDS {
+        let q = &queue;
+        let popped = std::thread::scope(|s| {
+            for t in 0..NUM_PUSHERS_POPPERS {
+                s.spawn(move || {
+                    for i in 0..N {
+                        q.push(f(round * N * NUM_PUSHERS_POPPERS + t * N + i));
+                    }
+                });
+            }
+
+            let poppers: Vec<_> = (0..NUM_PUSHERS_POPPERS)
+                .map(|_| {
+                    s.spawn(move || (0..num_popped).filter_map(|_| q.pop()).collect::<Vec<_>>())
+                })
+                .collect();
+            poppers
+                .into_iter()
+                .flat_map(|x| x.join().expect("popper panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        expected
+            .extend((0..(N * NUM_PUSHERS_POPPERS)).map(|i| f(round * N * NUM_PUSHERS_POPPERS + i)));
+        for x in &popped {
+            let idx = expected
+                .iter()
+                .position(|y| y == x)
+                .expect("popped an unknown element");
+            _ = expected.remove(idx);
+        }
+
+        let len = queue.len();
+        queue.compact();
+
+        assert_eq!(queue.len(), len);
+        assert_eq!(queue.num_write_reserved(Ordering::Relaxed), len);
+        assert!(queue.capacity() >= len);
+
+        let mut in_queue: Vec<_> = queue.iter().cloned().collect();
+        in_queue.sort();
+        let mut sorted_expected = expected.clone();
+        sorted_expected.sort();
+        assert_eq!(in_queue, sorted_expected);
+    }
+
+    let mut collected: Vec<_> = core::iter::from_fn(|| queue.pop()).collect();
+    collected.sort();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn compact_releases_fragments() {
+    let mut queue = ConcurrentQueue::with_linear_growth(4, 64);
+    queue.extend((0..1000).map(|x| x.to_string()));
+    assert_eq!(queue.capacity(), 1008);
+
+    _ = queue.pull(980);
+    queue.compact();
+    assert_eq!(queue.capacity(), 32);
+    assert_eq!(
+        queue.iter().cloned().collect::<Vec<_>>(),
+        (980..1000).map(|x| x.to_string()).collect::<Vec<_>>()
+    );
+
+    _ = queue.pull(20);
+    queue.compact();
+    assert!(queue.is_empty());
+    assert_eq!(queue.num_write_reserved(Ordering::Relaxed), 0);
+
+    queue.extend((0..100).map(|x| x.to_string()));
+    assert_eq!(queue.pop(), Some(0.to_string()));
+    assert_eq!(queue.len(), 99);
+}
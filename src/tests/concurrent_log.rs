@@ -0,0 +1,105 @@
+use crate::ConcurrentLog;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{IntoConcurrentPinnedVec, PinnedVec};
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_WRITERS: usize = 4;
+const NUM_READERS: usize = 2;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_WRITERS * 2), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [1, 4]
+)]
+fn log_append_get<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, batch: usize)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Sync + Clone + Ord + Debug,
+{
+    let f = &f;
+    let log: ConcurrentLog<T, _> = vec.into();
+    let l = &log;
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_WRITERS {
+            s.spawn(move || {
+                let mut i = 0;
+                while i < N {
+                    match batch {
+                        1 => {
+                            let seq = l.append(f(t * N + i));
+                            assert_eq!(l.get(seq), Some(&f(t * N + i)));
+                            i += 1;
+                        }
+                        _ => {
+                            let n = batch.min(N - i);
+                            let range = l.extend((i..(i + n)).map(|j| f(t * N + j)));
+                            assert_eq!(range.end - range.start, n as u64);
+                            for (k, seq) in range.enumerate() {
+                                assert_eq!(l.get(seq), Some(&f(t * N + i + k)));
+                            }
+                            i += n;
+                        }
+                    }
+                }
+            });
+        }
+
+        for _ in 0..NUM_READERS {
+            s.spawn(move || {
+                let mut position = 0;
+                while position < (NUM_WRITERS * N) as u64 {
+                    position += l.iter_from(position).count() as u64;
+                    assert!(position <= l.next_seq());
+                }
+            });
+        }
+    });
+
+    assert_eq!(log.len(), NUM_WRITERS * N);
+    assert_eq!(log.next_seq(), (NUM_WRITERS * N) as u64);
+    assert_eq!(log.get((NUM_WRITERS * N) as u64), None);
+    assert_eq!(log.get(u64::MAX), None);
+
+    let mut replayed: Vec<_> = log.iter().cloned().collect();
+    replayed.sort();
+    let mut expected: Vec<_> = (0..(NUM_WRITERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(replayed, expected);
+}
+
+#[test]
+fn log_iter_from() {
+    let log = ConcurrentLog::new();
+    assert!(log.is_empty());
+    assert_eq!(log.iter_from(0).next(), None);
+
+    let range = log.extend((0..10).map(|x| x.to_string()));
+    assert_eq!(range, 0..10);
+    assert_eq!(log.extend(Vec::<String>::new()), 10..10);
+
+    let replayed: Vec<_> = log.iter_from(7).cloned().collect();
+    assert_eq!(replayed, ["7", "8", "9"]);
+
+    let mut iter = log.iter_from(9);
+    assert_eq!(iter.next().map(|x| x.as_str()), Some("9"));
+    assert_eq!(log.append(10.to_string()), 10);
+    assert_eq!(iter.next().map(|x| x.as_str()), Some("10"));
+    assert_eq!(iter.next(), None);
+    drop(iter);
+
+    assert_eq!(log.iter_from(u64::MAX).next(), None);
+    assert_eq!(log.iter_from(u64::MAX - 1).count(), 0);
+
+    let vec = log.into_inner();
+    assert_eq!(vec.len(), 11);
+}
@@ -0,0 +1,114 @@
+use crate::ConcurrentQueue;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::{SplitVec, prelude::PseudoDefault};
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 37;
+#[cfg(not(miri))]
+const N: usize = 1735;
+
+fn elements(range: core::ops::Range<usize>) -> Vec<String> {
+    range.map(|x| x.to_string()).collect()
+}
+
+#[test_matrix([0, 1, N])]
+fn from_std_collections(len: usize) {
+    let queue = ConcurrentQueue::from_vec(elements(0..len));
+    assert_eq!(queue.len(), len);
+    queue.push(len.to_string());
+    assert_eq!(queue.into_vec(), elements(0..(len + 1)));
+
+    let mut deque: VecDeque<_> = elements(1..len.max(1)).into();
+    deque.push_front(0.to_string());
+    let queue = ConcurrentQueue::from_vec_deque(deque);
+    assert_eq!(queue.pop(), Some(0.to_string()));
+    assert_eq!(
+        queue.into_vec_deque(),
+        VecDeque::from(elements(1..len.max(1)))
+    );
+
+    let queue = ConcurrentQueue::from_array([0, 1, 2].map(|x: usize| x.to_string()));
+    assert_eq!(queue.into_vec(), elements(0..3));
+}
+
+#[test_matrix(
+    [FixedVec::new(N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, N / 16 + 1)],
+    [0, 1, N / 3, N]
+)]
+fn into_vec<P>(vec: P, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<String> + PseudoDefault + Into<Vec<String>>,
+    P::ConPinnedVec: ConcurrentPinnedVec<String, P = P>,
+{
+    let queue = ConcurrentQueue::from(vec);
+    queue.extend(elements(0..N));
+    _ = queue.pull(num_popped);
+    assert_eq!(queue.into_vec(), elements(num_popped..N));
+}
+
+#[test]
+fn into_vec_reuses_fixed_vec_allocation() {
+    let mut vec = Vec::with_capacity(N);
+    vec.extend(elements(0..(N / 2)));
+    let ptr = vec.as_ptr();
+
+    let queue: ConcurrentQueue<_, _> = FixedVec::from(vec).into();
+    queue.extend(elements((N / 2)..N));
+
+    let vec = queue.into_vec();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec, elements(0..N));
+}
+
+#[cfg(feature = "concurrent-bag")]
+#[test_matrix([0, 1, N / 3, N])]
+fn concurrent_bag_round_trip(num_popped: usize) {
+    use orx_concurrent_bag::ConcurrentBag;
+
+    let bag = ConcurrentBag::new();
+    for x in elements(0..N) {
+        bag.push(x);
+    }
+
+    let queue = ConcurrentQueue::from_bag(bag);
+    assert_eq!(queue.len(), N);
+    _ = queue.pull(num_popped);
+    queue.push(N.to_string());
+
+    let bag: ConcurrentBag<_> = queue.into();
+    assert_eq!(bag.len(), N + 1 - num_popped);
+    assert_eq!(bag.into_inner().to_vec(), elements(num_popped..(N + 1)));
+}
+
+#[cfg(feature = "concurrent-vec")]
+#[test_matrix([0, 1, N / 3, N])]
+fn concurrent_vec_round_trip(num_popped: usize) {
+    use orx_concurrent_vec::ConcurrentVec;
+
+    let vec = ConcurrentVec::new();
+    vec.extend(elements(0..N));
+    let first = vec.get(0).map(|x| x as *const _);
+
+    let queue = ConcurrentQueue::from_concurrent_vec(vec);
+    assert_eq!(queue.len(), N);
+    let popped: Vec<_> = queue
+        .pull(num_popped)
+        .into_iter()
+        .flatten()
+        .map(|x| x.cloned())
+        .collect();
+    assert_eq!(popped, elements(0..num_popped.min(N)));
+
+    let vec: ConcurrentVec<_> = queue.into();
+    assert_eq!(vec.len(), N - num_popped.min(N));
+    assert_eq!(vec.clone_to_vec(), elements(num_popped.min(N)..N));
+    if num_popped == 0 {
+        // the storage is reused without moving the elements
+        assert_eq!(vec.get(0).map(|x| x as *const _), first);
+    }
+}
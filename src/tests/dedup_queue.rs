@@ -0,0 +1,125 @@
+use crate::{ConcurrentQueue, DedupConcurrentQueue, DedupPolicy};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [1, 4]
+)]
+fn dedup_concurrent_push<P>(vec: P, batch: usize)
+where
+    P: IntoConcurrentPinnedVec<String>,
+{
+    let queue: DedupConcurrentQueue<String, _> = vec.into();
+    let q = &queue;
+
+    std::thread::scope(|s| {
+        for _ in 0..NUM_PUSHERS {
+            s.spawn(move || match batch {
+                1 => {
+                    for i in 0..N {
+                        q.push(i.to_string());
+                    }
+                }
+                _ => {
+                    for i in (0..N).step_by(batch) {
+                        q.extend((i..(i + batch).min(N)).map(|x| x.to_string()));
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(queue.len(), N);
+    assert_eq!(queue.num_known(), N);
+
+    let mut popped: Vec<_> = core::iter::from_fn(|| queue.pop()).collect();
+    popped.sort();
+    let mut expected: Vec<_> = (0..N).map(|x| x.to_string()).collect();
+    expected.sort();
+    assert_eq!(popped, expected);
+    assert_eq!(queue.num_known(), 0);
+}
+
+#[test_matrix([DedupPolicy::Pending, DedupPolicy::EverSeen])]
+fn dedup_concurrent_push_pop(policy: DedupPolicy) {
+    let queue = DedupConcurrentQueue::from_queue(ConcurrentQueue::new(), policy);
+    let q = &queue;
+    let popped = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for _ in 0..NUM_PUSHERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(i % 17);
+                }
+            });
+        }
+        for _ in 0..NUM_PUSHERS {
+            let popped = &popped;
+            s.spawn(move || {
+                for _ in 0..N {
+                    if let Some(x) = q.pop() {
+                        popped.push(x);
+                    }
+                }
+            });
+        }
+    });
+
+    let mut popped = popped.into_inner().to_vec();
+    popped.extend(core::iter::from_fn(|| queue.pop()));
+    assert!(queue.is_empty());
+
+    match policy {
+        DedupPolicy::EverSeen => {
+            popped.sort();
+            assert_eq!(popped, (0..17).collect::<Vec<_>>());
+            assert_eq!(queue.num_known(), 17);
+        }
+        DedupPolicy::Pending => {
+            assert!(popped.len() >= 17);
+            assert_eq!(queue.num_known(), 0);
+        }
+    }
+}
+
+#[test]
+fn dedup_policy() {
+    let queue = DedupConcurrentQueue::new();
+    assert_eq!(queue.policy(), DedupPolicy::Pending);
+
+    assert!(queue.push('a'));
+    assert!(queue.contains(&'a'));
+    assert!(!queue.push('a'));
+    assert_eq!(queue.pop(), Some('a'));
+    assert!(!queue.contains(&'a'));
+    assert!(queue.push('a'));
+
+    let queue = DedupConcurrentQueue::with_policy(DedupPolicy::EverSeen);
+    assert!(queue.push('a'));
+    assert_eq!(queue.pop(), Some('a'));
+    assert!(queue.contains(&'a'));
+    assert!(!queue.push('a'));
+    assert!(queue.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn dedup_from_non_empty_queue() {
+    let queue = ConcurrentQueue::new();
+    queue.push(1);
+    let _ = DedupConcurrentQueue::from_queue(queue, DedupPolicy::Pending);
+}
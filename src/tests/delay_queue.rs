@@ -0,0 +1,102 @@
+use crate::{Clock, DelayQueue, ManualClock};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use std::time::Duration;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_POPPERS: usize = 4;
+
+#[test]
+fn delay_pop_only_due_elements() {
+    let queue = DelayQueue::with_clock(ManualClock::new());
+    for i in 0..N {
+        queue.push_after(Duration::from_millis((i % 10) as u64), i);
+    }
+
+    for ms in 0..10 {
+        let mut popped = Vec::new();
+        while let Some(x) = queue.pop() {
+            popped.push(x);
+        }
+
+        let expected: Vec<_> = (0..N).filter(|i| i % 10 == ms).collect();
+        assert_eq!(popped, expected);
+
+        queue.clock().advance(Duration::from_millis(1));
+    }
+
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn delay_concurrent_push_pop() {
+    let queue: DelayQueue<String, _> = DelayQueue::with_clock(ManualClock::new());
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_POPPERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    let delay = Duration::from_secs((i % 2) as u64);
+                    q.push_after(delay, (t * N + i).to_string());
+                }
+            });
+        }
+
+        for _ in 0..NUM_PUSHERS_POPPERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    if let Some(x) = q.pop() {
+                        collected.push(x);
+                    }
+                }
+            });
+        }
+    });
+
+    // poppers might finish before all ready elements are pushed
+    while let Some(x) = queue.pop() {
+        collected.push(x);
+    }
+    let now = queue.clock().now();
+    assert!(queue.next_deadline().is_none_or(|x| x > now));
+
+    queue.clock().advance(Duration::from_secs(1));
+    while let Some(x) = queue.pop() {
+        collected.push(x);
+    }
+    assert!(queue.is_empty());
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.sort();
+
+    let mut expected: Vec<_> = (0..N * NUM_PUSHERS_POPPERS)
+        .map(|x| x.to_string())
+        .collect();
+    expected.sort();
+
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn delay_pop_wait_wakes_up_for_earlier_deadline() {
+    let queue = DelayQueue::new();
+    queue.push_after(Duration::from_secs(60), 'a');
+
+    std::thread::scope(|s| {
+        let waiter = s.spawn(|| queue.pop_wait());
+        std::thread::sleep(Duration::from_millis(10));
+        queue.push('b');
+        assert_eq!(waiter.join().expect("waiter must not panic"), Some('b'));
+    });
+
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue.pop(), None);
+}
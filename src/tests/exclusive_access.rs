@@ -0,0 +1,253 @@
+use crate::queue::ConcurrentQueue;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Bound;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::{SplitVec, prelude::PseudoDefault};
+use std::fmt::Debug;
+use test_case::{test_case, test_matrix};
+
+#[cfg(miri)]
+const N: usize = 37;
+#[cfg(not(miri))]
+const N: usize = 1735;
+
+fn queue_and_expected<P, T>(
+    vec: P,
+    f: impl Fn(usize) -> T,
+    num_popped: usize,
+) -> (ConcurrentQueue<T, P::ConPinnedVec>, VecDeque<T>)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send,
+{
+    let queue = ConcurrentQueue::from(vec);
+    queue.extend((0..N).map(&f));
+    let mut expected: VecDeque<_> = (0..N).map(&f).collect();
+    for _ in 0..num_popped {
+        _ = queue.pop();
+        _ = expected.pop_front();
+    }
+    (queue, expected)
+}
+
+#[test_matrix(
+    [FixedVec::new(2 * N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, N / 16 + 1)],
+    [|x| x, |x| x.to_string()],
+    [0, N / 3, N]
+)]
+fn get_front_back<P, T>(vec: P, f: impl Fn(usize) -> T, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send + Clone + PartialEq + Debug,
+{
+    let (mut queue, mut expected) = queue_and_expected(vec, &f, num_popped);
+
+    assert_eq!(queue.front(), expected.front());
+    assert_eq!(queue.back(), expected.back());
+    for i in 0..(expected.len() + 2) {
+        assert_eq!(queue.get(i), expected.get(i));
+    }
+
+    if let Some(x) = queue.get_mut(1) {
+        *x = f(N + 1);
+    }
+    if let Some(x) = expected.get_mut(1) {
+        *x = f(N + 1);
+    }
+
+    let slices: Vec<T> = queue.as_slices().flatten().cloned().collect();
+    assert_eq!(slices, Vec::from(expected));
+}
+
+#[test_matrix(
+    [FixedVec::new(2 * N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, N / 16 + 1)],
+    [|x| x, |x| x.to_string()],
+    [0, N / 3, N]
+)]
+fn retain<P, T>(vec: P, f: impl Fn(usize) -> T, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send + Clone + PartialEq + Debug,
+{
+    let (mut queue, mut expected) = queue_and_expected(vec, &f, num_popped);
+    let removed: Vec<_> = (0..N).filter(|x| x % 3 != 1).map(&f).collect();
+
+    queue.retain(|x| !removed.contains(x));
+    expected.retain(|x| !removed.contains(x));
+    assert_eq!(queue.len(), expected.len());
+
+    queue.push(f(N));
+    expected.push_back(f(N));
+    assert_eq!(
+        queue.iter().cloned().collect::<Vec<_>>(),
+        Vec::from(expected)
+    );
+}
+
+#[test]
+fn retain_with_panicking_predicate() {
+    let mut queue = ConcurrentQueue::new();
+    queue.extend((0..20).map(|x| x.to_string()));
+    _ = queue.pop();
+
+    let mut count = 0;
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        queue.retain(|x| {
+            count += 1;
+            assert!(count < 10);
+            x.len() > 1
+        })
+    }));
+    assert!(result.is_err());
+
+    let expected: Vec<_> = (10..20).map(|x| x.to_string()).collect();
+    assert_eq!(queue.iter().cloned().collect::<Vec<_>>(), expected);
+}
+
+#[test_matrix(
+    [FixedVec::new(2 * N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, N / 16 + 1)],
+    [|x| x, |x| x.to_string()],
+    [0, N / 3]
+)]
+fn drain<P, T>(vec: P, f: impl Fn(usize) -> T, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send + Clone + PartialEq + Debug,
+{
+    let (mut queue, mut expected) = queue_and_expected(vec, &f, num_popped);
+
+    // middle, partially consumed from both ends
+    let mut drain = queue.drain(10..60);
+    let mut expected_drain = expected.drain(10..60);
+    assert_eq!(drain.len(), 50);
+    for _ in 0..5 {
+        assert_eq!(drain.next(), expected_drain.next());
+        assert_eq!(drain.next_back(), expected_drain.next_back());
+    }
+    drop(drain);
+    drop(expected_drain);
+    assert_eq!(queue.len(), expected.len());
+
+    // front
+    let drained: Vec<_> = queue.drain(..7).collect();
+    let expected_drained: Vec<_> = expected.drain(..7).collect();
+    assert_eq!(drained, expected_drained);
+
+    // back
+    let len = queue.len();
+    let drained: Vec<_> = queue.drain((len - 3)..=(len - 1)).collect();
+    let expected_drained: Vec<_> = expected.drain((len - 3)..).collect();
+    assert_eq!(drained, expected_drained);
+
+    // empty
+    assert_eq!(queue.drain(5..5).count(), 0);
+
+    // forgotten
+    core::mem::forget(queue.drain(20..));
+    expected.truncate(20);
+    assert_eq!(queue.len(), expected.len());
+
+    queue.push(f(N));
+    expected.push_back(f(N));
+    assert_eq!(
+        queue.iter().cloned().collect::<Vec<_>>(),
+        Vec::from(expected)
+    );
+}
+
+#[test]
+#[should_panic]
+fn drain_out_of_bounds() {
+    let mut queue = ConcurrentQueue::new();
+    queue.extend(0..10);
+    _ = queue.pop();
+    _ = queue.drain(5..10);
+}
+
+#[test_case((Bound::Excluded(usize::MAX), Bound::Unbounded))]
+#[test_case((Bound::Included(0), Bound::Included(usize::MAX)))]
+#[should_panic(expected = "is out of bounds of the queue with length 9")]
+fn drain_bounds_beyond_max_usize(range: (Bound<usize>, Bound<usize>)) {
+    let mut queue = ConcurrentQueue::new();
+    queue.extend(0..10);
+    _ = queue.pop();
+    _ = queue.drain(range);
+}
+
+#[test_matrix(
+    [FixedVec::new(2 * N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(4, N / 16 + 1)],
+    [|x| x, |x| x.to_string()],
+    [0, N / 3, N]
+)]
+fn clear_and_truncate<P, T>(vec: P, f: impl Fn(usize) -> T, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send + Clone + PartialEq + Debug,
+{
+    let (mut queue, mut expected) = queue_and_expected(vec, &f, num_popped);
+
+    queue.truncate(N);
+    queue.truncate(100);
+    expected.truncate(100);
+    assert_eq!(
+        queue.iter().cloned().collect::<Vec<_>>(),
+        Vec::from(expected)
+    );
+
+    queue.clear();
+    assert!(queue.is_empty());
+    assert_eq!(queue.front(), None);
+
+    queue.extend((0..N).map(&f));
+    let expected: Vec<_> = (0..N).map(&f).collect();
+    assert_eq!(queue.iter().cloned().collect::<Vec<_>>(), expected);
+}
+
+#[test_matrix(
+    [FixedVec::new(2 * N), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(8, N / 64 + 1)],
+    [|x| x, |x| x.to_string()],
+    [0, 3, 200, N / 3, N]
+)]
+fn make_contiguous<P, T>(vec: P, f: impl Fn(usize) -> T, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<T, P = P>,
+    T: Send + Clone + PartialEq + Debug,
+{
+    let (mut queue, expected) = queue_and_expected(vec, &f, num_popped);
+    let expected = Vec::from(expected);
+    let num_slices = queue.as_slices().count();
+
+    match queue.make_contiguous() {
+        Some(slice) => {
+            assert_eq!(slice, expected.as_slice());
+            assert!(queue.as_slices().count() <= 1);
+        }
+        None => assert_eq!(queue.as_slices().count(), num_slices),
+    }
+
+    queue.push(f(N));
+    let mut expected = expected;
+    expected.push(f(N));
+    assert_eq!(queue.iter().cloned().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn make_contiguous_moves_to_front_fragment() {
+    let mut queue: ConcurrentQueue<String, _> = ConcurrentQueue::with_linear_growth(4, 8);
+    queue.extend((0..24).map(|x| x.to_string()));
+    _ = queue.pull(14);
+    assert_eq!(queue.as_slices().count(), 2);
+
+    let expected: Vec<_> = (14..24).map(|x| x.to_string()).collect();
+    assert_eq!(queue.make_contiguous().map(|x| x.to_vec()), Some(expected));
+    assert_eq!(queue.as_slices().count(), 1);
+}
@@ -0,0 +1,98 @@
+use crate::LeasedQueue;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_WORKERS: usize = 4;
+
+#[test_matrix([1, 2, 5])]
+fn leased_redelivery_and_dead_letters(max_deliveries: usize) {
+    let queue: LeasedQueue<String> = LeasedQueue::with_max_deliveries(max_deliveries);
+    queue.extend((0..N).map(|x| x.to_string()));
+
+    let acked = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for _ in 0..NUM_WORKERS {
+            s.spawn(|| {
+                while let Some(lease) = queue.pop_leased() {
+                    assert!(lease.num_deliveries() <= max_deliveries);
+                    let x: usize = lease.parse().expect("is a number");
+                    // multiples of 3 always fail, multiples of 2 fail on the first delivery
+                    match (
+                        x.is_multiple_of(3),
+                        x.is_multiple_of(2) && lease.num_deliveries() == 1,
+                    ) {
+                        (true, _) => lease.nack(),
+                        (false, true) => drop(lease),
+                        (false, false) => _ = acked.push(lease.ack()),
+                    }
+                }
+            });
+        }
+    });
+
+    let mut acked: Vec<usize> = acked
+        .into_inner()
+        .to_vec()
+        .iter()
+        .map(|x| x.parse().expect("is a number"))
+        .collect();
+    acked.sort();
+
+    let mut dead: Vec<usize> = queue
+        .dead_letters()
+        .pull(N)
+        .map(|x| x.map(|x| x.parse().expect("is a number")).collect())
+        .unwrap_or_default();
+    dead.sort();
+
+    let is_dead = |x: usize| x.is_multiple_of(3) || (max_deliveries == 1 && x.is_multiple_of(2));
+    let expected_acked: Vec<_> = (0..N).filter(|x| !is_dead(*x)).collect();
+    let expected_dead: Vec<_> = (0..N).filter(|x| is_dead(*x)).collect();
+
+    assert_eq!(acked, expected_acked);
+    assert_eq!(dead, expected_dead);
+    assert!(queue.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn leased_expired_leases_are_redelivered() {
+    use std::time::Duration;
+
+    let queue = LeasedQueue::with_max_deliveries(2);
+    queue.extend((0..10).map(|x| x.to_string()));
+
+    let hung: Vec<_> = (0..4)
+        .map(|_| {
+            queue
+                .pop_leased_for(Duration::from_millis(20))
+                .expect("non-empty")
+        })
+        .collect();
+
+    assert_eq!(queue.len(), 6);
+    assert_eq!(queue.redeliver_expired(), 0);
+
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(queue.redeliver_expired(), 4);
+    assert_eq!(queue.len(), 10);
+
+    // expired leases are not redelivered twice
+    drop(hung);
+    assert_eq!(queue.len(), 10);
+
+    let mut acked = Vec::new();
+    while let Some(lease) = queue.pop_leased_for(Duration::from_secs(60)) {
+        acked.push(lease.ack());
+    }
+    assert_eq!(acked.len(), 10);
+    assert_eq!(queue.redeliver_expired(), 0);
+}
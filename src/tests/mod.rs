@@ -1,8 +1,39 @@
+mod allocation;
+#[cfg(feature = "std")]
+mod broadcast;
+mod builder;
+mod compact;
+mod concurrent_log;
+mod conversions;
+#[cfg(feature = "std")]
+mod dedup_queue;
+#[cfg(feature = "std")]
+mod delay_queue;
+mod exclusive_access;
 mod extend;
 mod into_inner;
+mod leased;
+#[cfg(feature = "std")]
+mod ordered_collector;
+#[cfg(feature = "std")]
+mod par_map;
+#[cfg(feature = "std")]
+mod partitioned;
 mod pop;
+mod priority_queue;
 mod pull;
+mod pull_adaptive;
+#[cfg(feature = "std")]
+mod pull_batch;
+mod pull_exact;
 mod pull_extend;
+mod pull_returnable;
+mod pull_slices;
+mod pull_weighted;
 mod pull_without_consuming_all;
 mod push;
 mod push_pop;
+mod reserve_max_capacity;
+mod sharded_queue;
+mod snapshot;
+mod work_stealing;
@@ -0,0 +1,90 @@
+use crate::{ConcurrentQueue, OrderedCollector};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_WORKERS: usize = 4;
+
+#[test_matrix([1, 7, 64], [1, 4])]
+fn ordered_collector_restores_order(capacity: usize, chunk_size: usize) {
+    let queue = ConcurrentQueue::new();
+    queue.extend(0..N);
+    let collector = OrderedCollector::new(capacity);
+    let mut output = Vec::new();
+
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            std::thread::scope(|s| {
+                for _ in 0..NUM_WORKERS {
+                    s.spawn(|| match chunk_size {
+                        1 => {
+                            while let Some((idx, x)) = queue.pop_with_idx() {
+                                collector.push(idx, x.to_string());
+                            }
+                        }
+                        _ => {
+                            while let Some((begin_idx, chunk)) = queue.pull_with_idx(chunk_size) {
+                                collector.extend(begin_idx, chunk.map(|x| x.to_string()));
+                            }
+                        }
+                    });
+                }
+            });
+            collector.close();
+        });
+
+        for x in collector.iter() {
+            assert!(collector.num_buffered() <= capacity);
+            output.push(x);
+        }
+    });
+
+    let expected: Vec<String> = (0..N).map(|x| x.to_string()).collect();
+    assert_eq!(output, expected);
+    assert_eq!(collector.next_idx(), N);
+    assert_eq!(collector.num_buffered(), 0);
+}
+
+#[test]
+fn ordered_collector_window() {
+    let collector = OrderedCollector::with_first_idx(3, 10);
+    assert_eq!(collector.capacity(), 3);
+    assert!(!collector.is_closed());
+
+    assert_eq!(collector.try_push(12, 'c'), Ok(()));
+    assert_eq!(collector.try_push(13, 'd'), Err('d'));
+    assert_eq!(collector.num_buffered(), 1);
+    assert_eq!(collector.pop(), None);
+
+    collector.extend(10, ['a', 'b']);
+    assert_eq!(collector.num_buffered(), 3);
+    assert_eq!(collector.pop(), Some('a'));
+    assert_eq!(collector.try_push(13, 'd'), Ok(()));
+
+    collector.close();
+    assert!(collector.is_closed());
+    assert_eq!(collector.iter().collect::<Vec<_>>(), ['b', 'c', 'd']);
+    assert_eq!(collector.pop_wait(), None);
+}
+
+#[test]
+#[should_panic]
+fn ordered_collector_duplicate_idx() {
+    let collector = OrderedCollector::new(4);
+    collector.push(1, 'a');
+    collector.push(1, 'b');
+}
+
+#[test]
+#[should_panic]
+fn ordered_collector_already_received_idx() {
+    let collector = OrderedCollector::new(4);
+    collector.push(0, 'a');
+    _ = collector.pop();
+    collector.push(0, 'b');
+}
@@ -0,0 +1,143 @@
+use crate::ConcurrentQueue;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS: usize = 2;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS + 10), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [1, 4],
+    [1, 7, 64]
+)]
+fn par_map_ordered<P>(vec: P, num_threads: usize, chunk_size: usize)
+where
+    P: IntoConcurrentPinnedVec<usize>,
+{
+    let queue: ConcurrentQueue<usize, _> = vec.into();
+    queue.extend(0..N);
+    _ = queue.pop();
+
+    let output = queue.par_map_ordered(num_threads, chunk_size, |x| x.to_string());
+
+    let expected: Vec<String> = (1..N).map(|x| x.to_string()).collect();
+    assert_eq!(output, expected);
+    assert!(queue.is_empty());
+}
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS + 10), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [1, 4],
+    [1, 7, 64]
+)]
+fn par_map_ordered_until<P>(vec: P, num_threads: usize, chunk_size: usize)
+where
+    P: IntoConcurrentPinnedVec<usize>,
+{
+    let queue: ConcurrentQueue<usize, _> = vec.into();
+    let closed = AtomicBool::new(false);
+    let q = &queue;
+
+    let output = std::thread::scope(|s| {
+        let closed = &closed;
+        s.spawn(move || {
+            std::thread::scope(|s| {
+                for t in 0..NUM_PUSHERS {
+                    s.spawn(move || {
+                        for i in 0..N {
+                            q.push(t * N + i);
+                        }
+                    });
+                }
+            });
+            closed.store(true, Ordering::Release);
+        });
+
+        q.par_map_ordered_until(
+            num_threads,
+            chunk_size,
+            || closed.load(Ordering::Acquire),
+            |x| x * 2,
+        )
+    });
+
+    assert_eq!(output.len(), NUM_PUSHERS * N);
+    for t in 0..NUM_PUSHERS {
+        let of_pusher: Vec<_> = output.iter().filter(|x| **x / 2 / N == t).collect();
+        let expected: Vec<_> = (0..N).map(|i| (t * N + i) * 2).collect();
+        assert_eq!(of_pusher, expected.iter().collect::<Vec<_>>());
+    }
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn par_map_ordered_empty() {
+    let queue = ConcurrentQueue::<usize>::new();
+    assert!(queue.par_map_ordered(4, 8, |x| x.to_string()).is_empty());
+
+    queue.extend(0..10);
+    _ = queue.pull(10);
+    assert!(queue.par_map_ordered(4, 8, |x| x.to_string()).is_empty());
+}
+
+#[test]
+fn par_map_ordered_panicking_map() {
+    static NUM_DROPPED: AtomicUsize = AtomicUsize::new(0);
+    static NUM_MAPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(dead_code)] String);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            NUM_DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let queue = ConcurrentQueue::new();
+    queue.extend(0..N);
+
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        queue.par_map_ordered(4, 8, |x| {
+            assert!(x != N / 2);
+            NUM_MAPPED.fetch_add(1, Ordering::Relaxed);
+            Counted(x.to_string())
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(
+        NUM_DROPPED.load(Ordering::Relaxed),
+        NUM_MAPPED.load(Ordering::Relaxed)
+    );
+}
+
+#[test]
+fn par_map_ordered_until_with_concurrent_pops() {
+    let queue = ConcurrentQueue::new();
+    queue.extend(0..N);
+    let closed = AtomicBool::new(true);
+
+    let (output, num_popped) = std::thread::scope(|s| {
+        let popper = s.spawn(|| {
+            let mut num_popped = 0;
+            while queue.pop().is_some() {
+                num_popped += 1;
+            }
+            num_popped
+        });
+        let output =
+            queue.par_map_ordered_until(2, 3, || closed.load(Ordering::Relaxed), |x| x.to_string());
+        (output, popper.join().expect("popper must not panic"))
+    });
+
+    // results of the elements popped by the other thread are missing, the others are in order
+    assert_eq!(output.len() + num_popped, N);
+    assert!(output.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,130 @@
+use crate::PartitionedQueue;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS: usize = 4;
+const NUM_KEYS: usize = 13;
+
+#[test_matrix([1, 4, 8], [1, 3, 4], [1, 5])]
+fn partitioned_per_key_order(num_partitions: usize, num_consumers: usize, chunk_size: usize) {
+    let queue = PartitionedQueue::new(num_partitions);
+    let q = &queue;
+    let in_progress: Vec<_> = (0..NUM_KEYS).map(|_| AtomicBool::new(false)).collect();
+    let processed: Vec<_> = (0..NUM_KEYS).map(|_| Mutex::new(Vec::new())).collect();
+    let num_pushed = AtomicUsize::new(0);
+
+    let process = |key: usize, value: (usize, usize)| {
+        assert!(!in_progress[key].swap(true, Ordering::Relaxed));
+        processed[key].lock().expect("is not poisoned").push(value);
+        in_progress[key].store(false, Ordering::Relaxed);
+    };
+    let process = &process;
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS {
+            let num_pushed = &num_pushed;
+            s.spawn(move || {
+                for i in 0..N {
+                    let key = (t + i) % NUM_KEYS;
+                    q.push(&key, (key, t, i));
+                    _ = num_pushed.fetch_add(1, Ordering::Release);
+                }
+            });
+        }
+
+        for _ in 0..num_consumers {
+            let num_pushed = &num_pushed;
+            s.spawn(move || {
+                let mut consumer = q.consumer();
+                loop {
+                    let done = num_pushed.load(Ordering::Acquire) == NUM_PUSHERS * N;
+                    let mut received = false;
+                    match chunk_size {
+                        1 => {
+                            if let Some((key, t, i)) = consumer.pop() {
+                                received = true;
+                                process(key, (t, i));
+                            }
+                        }
+                        _ => {
+                            if let Some(chunk) = consumer.pull(chunk_size) {
+                                received = true;
+                                for (key, t, i) in chunk {
+                                    process(key, (t, i));
+                                }
+                            }
+                        }
+                    }
+                    if done && !received && q.is_empty() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    assert!(queue.is_empty());
+
+    let mut total = 0;
+    for values in processed {
+        let values = values.into_inner().expect("is not poisoned");
+        total += values.len();
+        for t in 0..NUM_PUSHERS {
+            let of_pusher: Vec<_> = values.iter().filter(|x| x.0 == t).map(|x| x.1).collect();
+            assert!(of_pusher.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+    assert_eq!(total, NUM_PUSHERS * N);
+}
+
+#[test]
+fn partitioned_claims() {
+    let queue = PartitionedQueue::new(1);
+    queue.extend(&'a', [0, 1, 2]);
+    queue.push(&'b', 3);
+
+    let mut c1 = queue.consumer();
+    let mut c2 = queue.consumer();
+
+    assert_eq!(c1.pop(), Some(0));
+    assert_eq!(c1.claimed_partition(), Some(0));
+    assert_eq!(c2.pop(), None);
+
+    c1.release();
+    assert_eq!(c1.claimed_partition(), None);
+    assert_eq!(c2.pop(), Some(1));
+    assert_eq!(c1.pop(), None);
+
+    drop(c2);
+    assert_eq!(c1.pop(), Some(2));
+    assert_eq!(c1.pop(), Some(3));
+    assert_eq!(c1.pop(), None);
+    assert_eq!(c1.claimed_partition(), None);
+}
+
+#[test]
+fn partitioned_round_robin() {
+    let queue = PartitionedQueue::new(8);
+    for key in 0..8 {
+        queue.extend(&key, [key; 3]);
+    }
+    let num_used_partitions = (0..8)
+        .map(|key| queue.partition_of(&key))
+        .collect::<std::collections::BTreeSet<_>>()
+        .len();
+
+    let mut consumer = queue.consumer();
+    let first: Vec<_> = (0..num_used_partitions)
+        .map(|_| consumer.pop().expect("is not empty"))
+        .collect();
+    let partitions: std::collections::BTreeSet<_> =
+        first.iter().map(|key| queue.partition_of(key)).collect();
+    assert_eq!(partitions.len(), num_used_partitions);
+}
@@ -0,0 +1,102 @@
+use crate::{ConcurrentPriorityQueue, ConcurrentQueue};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const LEVELS: usize = 3;
+const NUM_PUSHERS_POPPERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_POPPERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [1, 5]
+)]
+fn priority_push_pop_pull<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, chunk_size: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + Clone,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let levels = core::array::from_fn(|_| ConcurrentQueue::from(vec.clone()));
+    let queue: ConcurrentPriorityQueue<T, LEVELS, _> = ConcurrentPriorityQueue::from_levels(levels);
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_POPPERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(i % LEVELS, f(t * N + i));
+                }
+            });
+        }
+
+        for _ in 0..NUM_PUSHERS_POPPERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    match chunk_size {
+                        1 => {
+                            if let Some(x) = q.pop() {
+                                collected.push(x);
+                            }
+                        }
+                        _ => {
+                            if let Some((_, chunk)) = q.pull(chunk_size) {
+                                collected.extend(chunk);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    while let Some(x) = queue.pop() {
+        collected.push(x);
+    }
+    assert!(queue.is_empty());
+    assert_eq!(queue.len(), 0);
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.sort();
+
+    let mut expected: Vec<_> = (0..N * NUM_PUSHERS_POPPERS).map(f).collect();
+    expected.sort();
+
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn priority_serves_highest_level_first() {
+    let queue: ConcurrentPriorityQueue<_, LEVELS> = ConcurrentPriorityQueue::new();
+    for i in 0..30 {
+        queue.push(i % LEVELS, i);
+    }
+
+    let mut popped = Vec::new();
+    while let Some((p, x)) = queue.pop_with_priority() {
+        popped.push((p, x));
+    }
+
+    let mut expected: Vec<_> = (0..30).map(|i| (i % LEVELS, i)).collect();
+    expected.sort_by_key(|(p, _)| LEVELS - p);
+    assert_eq!(popped, expected);
+}
+
+#[test]
+#[should_panic]
+fn priority_out_of_levels() {
+    let queue: ConcurrentPriorityQueue<_, LEVELS> = ConcurrentPriorityQueue::new();
+    queue.push(LEVELS, 42);
+}
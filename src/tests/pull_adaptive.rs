@@ -0,0 +1,116 @@
+use crate::{ChunkPolicy, ConcurrentQueue, PullMetrics};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_PULLERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_PULLERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [ChunkPolicy::Fixed(7), ChunkPolicy::guided(NUM_PUSHERS_PULLERS), ChunkPolicy::default()]
+)]
+fn pull_adaptive_concurrent<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, policy: ChunkPolicy)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue: ConcurrentQueue<T, _> = vec.into();
+    let queue = queue.with_chunk_policy(policy);
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_PULLERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+            });
+        }
+
+        for _ in 0..NUM_PUSHERS_PULLERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    if let Some(chunk) = q.pull_adaptive() {
+                        collected.extend(chunk);
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    while let Some(chunk) = queue.pull_adaptive() {
+        collected.extend(chunk);
+    }
+
+    let metrics = queue.pull_metrics();
+    assert_eq!(metrics.num_pulled, NUM_PUSHERS_PULLERS * N);
+    assert!(metrics.num_empty_pulls >= 1);
+    assert!(metrics.max_chunk_size >= 1);
+    if let ChunkPolicy::Fixed(chunk_size) = policy {
+        assert!(metrics.max_chunk_size <= chunk_size);
+    }
+
+    collected.sort();
+    let mut expected: Vec<_> = (0..(NUM_PUSHERS_PULLERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn chunk_policy_chunk_size() {
+    let policy = ChunkPolicy::Guided {
+        num_consumers: 4,
+        min_chunk_size: 2,
+        max_chunk_size: 10,
+    };
+    assert_eq!(policy.chunk_size(0), 2);
+    assert_eq!(policy.chunk_size(5), 2);
+    assert_eq!(policy.chunk_size(20), 5);
+    assert_eq!(policy.chunk_size(1000), 10);
+
+    assert_eq!(ChunkPolicy::guided(0).chunk_size(10), 10);
+    assert_eq!(ChunkPolicy::Fixed(0).chunk_size(10), 1);
+}
+
+#[test]
+fn pull_metrics() {
+    let mut queue = ConcurrentQueue::new();
+    assert_eq!(queue.chunk_policy(), ChunkPolicy::default());
+    queue.set_chunk_policy(ChunkPolicy::Fixed(3));
+    assert_eq!(queue.chunk_policy(), ChunkPolicy::Fixed(3));
+
+    queue.extend(0..10);
+    assert_eq!(queue.pull_metrics(), PullMetrics::default());
+    assert_eq!(queue.pull_metrics().average_chunk_size(), None);
+
+    while queue.pull_adaptive().is_some() {}
+    let metrics = queue.pull_metrics();
+    assert_eq!(
+        metrics,
+        PullMetrics {
+            num_pulls: 4,
+            num_empty_pulls: 1,
+            num_pulled: 10,
+            max_chunk_size: 3,
+        }
+    );
+    assert_eq!(metrics.average_chunk_size(), Some(2.5));
+
+    queue.reset_pull_metrics();
+    assert_eq!(queue.pull_metrics(), PullMetrics::default());
+}
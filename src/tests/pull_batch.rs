@@ -0,0 +1,108 @@
+use crate::{ConcurrentQueue, WaitStrategy};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use std::time::Duration;
+use test_case::{test_case, test_matrix};
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_PULLERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_PULLERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [(1, 1), (1, 8), (4, 16)]
+)]
+fn pull_batch_concurrent<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, (min, max): (usize, usize))
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue: ConcurrentQueue<T, _> = vec.into();
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_PULLERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+            });
+        }
+
+        for _ in 0..NUM_PUSHERS_PULLERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..(N / max + 1) {
+                    if let Some(batch) = q.pull_batch(min, max, Duration::from_micros(200)) {
+                        assert!((min..=max).contains(&batch.len()));
+                        collected.extend(batch);
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    while let Some(batch) = queue.pull_batch(1, max, Duration::ZERO) {
+        collected.extend(batch);
+    }
+    assert!(queue.is_empty());
+
+    collected.sort();
+    let mut expected: Vec<_> = (0..(NUM_PUSHERS_PULLERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test_case(WaitStrategy::default())]
+#[test_case(WaitStrategy::Spin)]
+#[test_case(WaitStrategy::Yield)]
+#[test_case(WaitStrategy::YieldThenSleep { num_yields: 0, max_sleep: Duration::from_millis(1) })]
+fn pull_batch_waits_for_min(wait_strategy: WaitStrategy) {
+    let queue = ConcurrentQueue::new().with_wait_strategy(wait_strategy);
+    queue.extend([0, 1]);
+
+    assert!(queue.pull_batch(3, 4, Duration::from_millis(1)).is_none());
+    assert_eq!(queue.len(), 2);
+
+    let batch = std::thread::scope(|s| {
+        s.spawn(|| {
+            std::thread::sleep(Duration::from_millis(5));
+            queue.extend([2, 3]);
+        });
+        queue.pull_batch(3, 4, Duration::from_secs(10))
+    });
+    assert_eq!(
+        batch.map(|x| x.collect::<Vec<_>>()),
+        Some(alloc::vec![0, 1, 2, 3])
+    );
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn pull_batch_zero_min() {
+    let queue = ConcurrentQueue::<char>::new();
+    assert!(queue.pull_batch(0, 4, Duration::ZERO).is_none());
+
+    queue.push('a');
+    let batch = queue.pull_batch(0, 4, Duration::ZERO);
+    assert_eq!(batch.map(|x| x.collect::<Vec<_>>()), Some(alloc::vec!['a']));
+}
+
+#[test]
+#[should_panic]
+fn pull_batch_min_greater_than_max() {
+    let queue = ConcurrentQueue::<char>::new();
+    _ = queue.pull_batch(4, 2, Duration::ZERO);
+}
@@ -0,0 +1,92 @@
+use crate::ConcurrentQueue;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_PULLERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_PULLERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [1, 4, 7]
+)]
+fn pull_exact_concurrent<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, n: usize)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue: ConcurrentQueue<T, _> = vec.into();
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_PULLERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+            });
+        }
+
+        for t in 0..NUM_PUSHERS_PULLERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    // mix with other pulls and pops
+                    let chunk = match t {
+                        0 => q.pull(n).map(|x| x.collect::<Vec<_>>()),
+                        1 => q.pop().map(|x| alloc::vec![x]),
+                        _ => q.pull_exact(n).map(|x| {
+                            assert_eq!(x.len(), n);
+                            x.collect()
+                        }),
+                    };
+                    if let Some(chunk) = chunk {
+                        collected.extend(chunk);
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    while let Some(x) = queue.pull_exact(n) {
+        collected.extend(x);
+    }
+    assert!(queue.len() < n);
+    collected.extend(core::iter::from_fn(|| queue.pop()));
+
+    collected.sort();
+    let mut expected: Vec<_> = (0..(NUM_PUSHERS_PULLERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn pull_exact_leaves_queue_untouched() {
+    let queue = ConcurrentQueue::new();
+    assert!(queue.pull_exact(1).is_none());
+    assert!(queue.pull_exact(0).is_none());
+
+    queue.extend(0..3);
+    assert!(queue.pull_exact(4).is_none());
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.pop(), Some(0));
+
+    assert_eq!(
+        queue.pull_exact(2).map(|x| x.collect::<Vec<_>>()),
+        Some(alloc::vec![1, 2])
+    );
+    assert!(queue.is_empty());
+}
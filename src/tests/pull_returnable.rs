@@ -0,0 +1,130 @@
+use crate::ConcurrentQueue;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_PULLERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_PULLERS * 8), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, N * NUM_PUSHERS_PULLERS * 8 / 1024 + 1)],
+    [|x| x, |x| x.to_string()],
+    [1, 4, 7]
+)]
+fn pull_returnable_stopping_early<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, chunk_size: usize)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue: ConcurrentQueue<T, _> = vec.into();
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_PULLERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+            });
+        }
+
+        for _ in 0..NUM_PUSHERS_PULLERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    if let Some(chunk) = q.pull_returnable(chunk_size) {
+                        // consume only the first element, the rest is returned
+                        collected.extend(chunk.take(1));
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.extend(core::iter::from_fn(|| queue.pop()));
+    collected.sort();
+    let mut expected: Vec<_> = (0..(NUM_PUSHERS_PULLERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn pull_returnable_drop_remaining() {
+    let queue = ConcurrentQueue::new();
+    queue.extend((0..5).map(|x| x.to_string()));
+
+    assert!(queue.pull_returnable(0).is_none());
+
+    let chunk = queue.pull_returnable(3).expect("is not empty");
+    assert_eq!(chunk.len(), 3);
+    chunk.drop_remaining();
+    assert_eq!(queue.len(), 2);
+
+    let mut chunk = queue.pull_returnable(3).expect("is not empty");
+    assert_eq!(chunk.next().as_deref(), Some("3"));
+    assert_eq!(chunk.next().as_deref(), Some("4"));
+    assert_eq!(chunk.next(), None);
+    drop(chunk);
+    assert!(queue.is_empty());
+    assert!(queue.pull_returnable(1).is_none());
+}
+
+#[test]
+fn pull_returnable_out_of_capacity() {
+    let queue: ConcurrentQueue<_, _> = FixedVec::new(6).into();
+    queue.extend((0..5).map(|x| x.to_string()));
+
+    // room for a single returned element
+    let mut chunk = queue.pull_returnable(2).expect("is not empty");
+    assert_eq!(chunk.next().as_deref(), Some("0"));
+    drop(chunk);
+    assert_eq!(queue.len(), 4);
+
+    // no room for the returned elements; they are dropped
+    let mut chunk = queue.pull_returnable(3).expect("is not empty");
+    assert_eq!(chunk.next().as_deref(), Some("2"));
+    drop(chunk);
+    assert_eq!(queue.len(), 1);
+
+    // dropping the iterator while unwinding does not abort
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        let chunk = queue.pull_returnable(1);
+        assert!(chunk.is_none(), "consumer failed");
+    }));
+    assert!(result.is_err());
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn return_remaining_gives_back_what_does_not_fit() {
+    let queue: ConcurrentQueue<_, _> = FixedVec::new(4).into();
+    queue.extend((0..4).map(|x| x.to_string()));
+
+    let mut chunk = queue.pull_returnable(3).expect("is not empty");
+    assert_eq!(chunk.next().as_deref(), Some("0"));
+    let chunk = chunk
+        .return_remaining()
+        .expect_err("queue is out of capacity");
+    assert_eq!(chunk.len(), 2);
+    assert_eq!(queue.len(), 1);
+    assert_eq!(chunk.collect::<Vec<_>>(), ["1", "2"]);
+
+    let queue: ConcurrentQueue<_, _> = FixedVec::new(8).into();
+    queue.extend((0..4).map(|x| x.to_string()));
+    let mut chunk = queue.pull_returnable(3).expect("is not empty");
+    assert_eq!(chunk.next().as_deref(), Some("0"));
+    assert!(chunk.return_remaining().is_ok());
+    assert_eq!(queue.into_inner().to_vec(), ["3", "1", "2"]);
+}
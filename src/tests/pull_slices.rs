@@ -0,0 +1,102 @@
+use crate::ConcurrentQueue;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_PULLERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_PULLERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(3, 4096)],
+    [|x| x, |x| x.to_string()],
+    [1, 5, 33]
+)]
+fn pull_slices_concurrent<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, chunk_size: usize)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue: ConcurrentQueue<T, _> = vec.into();
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_PULLERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+            });
+        }
+
+        for t in 0..NUM_PUSHERS_PULLERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    if let Some(mut chunk) = q.pull_slices(chunk_size) {
+                        assert!(!chunk.is_empty());
+                        assert_eq!(chunk.slices().map(|x| x.len()).sum::<usize>(), chunk.len());
+                        for slice in chunk.slices_mut() {
+                            slice.sort_unstable();
+                        }
+                        match t % 2 {
+                            0 => _ = collected.extend(chunk),
+                            _ => {
+                                let mut vec = Vec::new();
+                                chunk.append_to(&mut vec);
+                                _ = collected.extend(vec);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.extend(core::iter::from_fn(|| queue.pop()));
+    collected.sort();
+    let mut expected: Vec<_> = (0..(NUM_PUSHERS_PULLERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn pull_slices_fragments() {
+    let queue = ConcurrentQueue::with_linear_growth(2, 16);
+    queue.extend((0..20).map(|x| x.to_string()));
+
+    assert!(queue.pull_slices(0).is_none());
+
+    let chunk = queue.pull_slices(3).expect("is not empty");
+    assert_eq!(chunk.begin_idx(), 0);
+    let slices: Vec<Vec<String>> = chunk.slices().map(|x| x.to_vec()).collect();
+    assert_eq!(slices, [["0", "1", "2"]]);
+    drop(chunk);
+
+    let chunk = queue.pull_slices(10).expect("is not empty");
+    assert_eq!(chunk.begin_idx(), 3);
+    assert_eq!(chunk.len(), 10);
+    let lengths: Vec<_> = chunk.slices().map(|x| x.len()).collect();
+    assert_eq!(lengths, [1, 4, 4, 1]);
+    let items: Vec<_> = chunk.into_iter().collect();
+    assert_eq!(items, (3..13).map(|x| x.to_string()).collect::<Vec<_>>());
+
+    let mut rest = Vec::new();
+    queue
+        .pull_slices(100)
+        .expect("is not empty")
+        .append_to(&mut rest);
+    assert_eq!(rest, (13..20).map(|x| x.to_string()).collect::<Vec<_>>());
+    assert!(queue.pull_slices(1).is_none());
+}
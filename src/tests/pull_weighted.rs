@@ -0,0 +1,121 @@
+use crate::ConcurrentQueue;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_PULLERS: usize = 4;
+
+fn varying_cost<T>() -> impl FnMut(&T) -> usize {
+    let mut i = 0;
+    move |_| {
+        i += 1;
+        i % 7
+    }
+}
+
+#[test_matrix(
+    [FixedVec::new(2 * N * NUM_PUSHERS_PULLERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [1, 10, 100]
+)]
+fn pull_weighted_concurrent<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, budget: usize)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Sync + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue: ConcurrentQueue<T, _> = vec.into();
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_PULLERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push(f(t * N + i));
+                }
+            });
+        }
+
+        for t in 0..NUM_PUSHERS_PULLERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    // mix with other pulls and pops
+                    let chunk = match t {
+                        0 => q.pull(3).map(|x| x.collect::<Vec<_>>()),
+                        1 => q.pop().map(|x| alloc::vec![x]),
+                        _ => q.pull_weighted(budget, varying_cost()).map(|x| x.collect()),
+                    };
+                    if let Some(chunk) = chunk {
+                        collected.extend(chunk);
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    while let Some(x) = queue.pull_weighted(budget, varying_cost()) {
+        collected.extend(x);
+    }
+
+    collected.sort();
+    let mut expected: Vec<_> = (0..(NUM_PUSHERS_PULLERS * N)).map(f).collect();
+    expected.sort();
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn pull_weighted_within_budget() {
+    let queue = ConcurrentQueue::new();
+    assert!(queue.pull_weighted(10, String::len).is_none());
+
+    queue.extend(["a", "bb", "ccc", "dddddddddddd", "", "e"].map(String::from));
+
+    let pull = |budget| {
+        queue
+            .pull_weighted(budget, String::len)
+            .map(|x| x.collect::<Vec<_>>())
+    };
+
+    assert_eq!(
+        pull(3),
+        Some(alloc::vec!["a".to_string(), "bb".to_string()])
+    );
+    assert_eq!(pull(0), Some(alloc::vec!["ccc".to_string()]));
+    assert_eq!(queue.len(), 3);
+    assert_eq!(pull(5), Some(alloc::vec!["dddddddddddd".to_string()]));
+    assert_eq!(pull(1), Some(alloc::vec!["".to_string(), "e".to_string()]));
+    assert_eq!(pull(1), None);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn pull_weighted_with_panicking_cost() {
+    let queue = ConcurrentQueue::new();
+    queue.extend((0..10).map(|x| x.to_string()));
+
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        queue.pull_weighted(100, |x: &String| {
+            assert!(x != "3");
+            1
+        })
+    }));
+    assert!(result.is_err());
+
+    // nothing is claimed; elements remain in the queue in their order
+    assert_eq!(queue.len(), 10);
+    let popped: Vec<_> = core::iter::from_fn(|| queue.pop()).collect();
+    assert_eq!(popped, (0..10).map(|x| x.to_string()).collect::<Vec<_>>());
+}
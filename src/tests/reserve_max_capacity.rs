@@ -0,0 +1,99 @@
+use crate::{ConcurrentQueue, ReserveMaxCapacityError};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::{SplitVec, prelude::PseudoDefault};
+use test_case::test_matrix;
+
+const NUM_PUSHERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(20), SplitVec::with_doubling_growth_and_fragments_capacity(3), SplitVec::with_linear_growth_and_fragments_capacity(2, 5)],
+    [0, 7]
+)]
+fn reserve_max_capacity<P>(vec: P, num_popped: usize)
+where
+    P: IntoConcurrentPinnedVec<String> + PseudoDefault,
+    P::ConPinnedVec: ConcurrentPinnedVec<String, P = P>,
+{
+    let mut queue: ConcurrentQueue<String, _> = vec.into();
+    let max_capacity = queue.max_capacity();
+    assert!(max_capacity >= 20);
+
+    queue.extend((0..20).map(|x| x.to_string()));
+    for _ in 0..num_popped {
+        _ = queue.pop();
+    }
+    assert_eq!(queue.remaining_capacity(), max_capacity - 20);
+    assert!(queue.capacity() >= 20);
+
+    let n = 1000;
+    let new_max_capacity = queue.reserve_max_capacity(n);
+    assert_eq!(new_max_capacity, queue.max_capacity());
+    assert!(queue.remaining_capacity() >= n);
+
+    // reserving what is already available does not change the capacity
+    assert_eq!(queue.reserve_max_capacity(n), new_max_capacity);
+
+    let q = &queue;
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS {
+            s.spawn(move || {
+                for i in 0..(n / NUM_PUSHERS) {
+                    q.push((20 + t * n / NUM_PUSHERS + i).to_string());
+                }
+            });
+        }
+    });
+
+    assert_eq!(queue.len(), 20 + n - num_popped);
+    let mut values: Vec<_> = queue.iter().cloned().collect();
+    values.sort();
+    let mut expected: Vec<_> = (num_popped..(20 + n)).map(|x| x.to_string()).collect();
+    expected.sort();
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn try_reserve_max_capacity_overflow() {
+    let mut queue = ConcurrentQueue::<String, _>::with_fixed_capacity(4);
+    queue.push('a'.to_string());
+    let overflow = Err(ReserveMaxCapacityError::CapacityOverflow { max_capacity: 4 });
+    assert_eq!(queue.try_reserve_max_capacity(usize::MAX), overflow);
+    assert_eq!(queue.try_reserve_max_capacity(usize::MAX / 2), overflow);
+    assert_eq!(queue.try_reserve_max_capacity(3), Ok(4));
+    assert_eq!(queue.pop(), Some('a'.to_string()));
+}
+
+#[test]
+#[should_panic]
+fn reserve_max_capacity_overflow() {
+    let mut queue = ConcurrentQueue::<String, _>::with_fixed_capacity(4);
+    queue.reserve_max_capacity(usize::MAX);
+}
+
+#[test]
+fn reserve_max_capacity_drops_each_element_once() {
+    struct Counted<'a>(&'a AtomicUsize);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            _ = self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let num_dropped = AtomicUsize::new(0);
+    let mut queue = ConcurrentQueue::with_fixed_capacity(8);
+    queue.extend((0..8).map(|_| Counted(&num_dropped)));
+    drop(queue.pull(5));
+    assert_eq!(num_dropped.load(Ordering::Relaxed), 5);
+
+    assert!(queue.reserve_max_capacity(100) >= 103);
+    assert_eq!(queue.len(), 3);
+    assert_eq!(num_dropped.load(Ordering::Relaxed), 5);
+
+    queue.push(Counted(&num_dropped));
+    drop(queue);
+    assert_eq!(num_dropped.load(Ordering::Relaxed), 9);
+}
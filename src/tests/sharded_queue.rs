@@ -0,0 +1,131 @@
+use crate::{ConcurrentQueue, ShardedConcurrentQueue};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_PUSHERS_POPPERS: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * NUM_PUSHERS_POPPERS), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [1, 3]
+)]
+fn sharded_push_pop<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, num_lanes: usize)
+where
+    P: IntoConcurrentPinnedVec<T> + Clone,
+    T: Send + Clone + Ord + Debug,
+{
+    let f = &f;
+    let queue = ShardedConcurrentQueue::from_lanes(
+        (0..num_lanes).map(|_| ConcurrentQueue::from(vec.clone())),
+    );
+    let q = &queue;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_PUSHERS_POPPERS {
+            s.spawn(move || {
+                for i in 0..N {
+                    q.push_to(t, f(t * N + i));
+                }
+            });
+        }
+
+        for t in 0..NUM_PUSHERS_POPPERS {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    if let Some(x) = q.pop_from(t) {
+                        collected.push(x);
+                    }
+                }
+            });
+        }
+    });
+
+    while let Some(x) = queue.pop_from(0) {
+        collected.push(x);
+    }
+    assert!(queue.is_empty());
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.sort();
+
+    let mut expected: Vec<_> = (0..N * NUM_PUSHERS_POPPERS).map(f).collect();
+    expected.sort();
+
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn sharded_per_lane_fifo() {
+    let queue = ShardedConcurrentQueue::new(3);
+    for i in 0..30 {
+        queue.push_to(i, i);
+    }
+
+    for lane in queue.lanes() {
+        assert_eq!(lane.len(), 10);
+    }
+
+    let lanes = queue.into_lanes();
+    for (l, lane) in lanes.into_iter().enumerate() {
+        let values: Vec<_> = lane.into_iter().collect();
+        let expected: Vec<_> = (0..10).map(|i| l + 3 * i).collect();
+        assert_eq!(values, expected);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sharded_push_pop_use_home_lane_of_thread() {
+    let queue = ShardedConcurrentQueue::new(4);
+    let queue = &queue;
+
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            s.spawn(move || {
+                for i in 0..100 {
+                    queue.push(t * 100 + i);
+                }
+                // the home lane of a thread is fixed
+                let lanes: Vec<_> = queue.lanes().map(|x| x.len()).collect();
+                assert!(lanes.iter().any(|len| *len >= 100));
+            });
+        }
+    });
+    assert_eq!(queue.len(), 400);
+
+    let values: Vec<_> = core::iter::from_fn(|| queue.pop()).collect();
+    assert_eq!(values.len(), 400);
+    // elements pushed by the same thread are popped in order
+    for t in 0..4 {
+        let of_thread: Vec<_> = values.iter().copied().filter(|x| x / 100 == t).collect();
+        assert_eq!(of_thread, (t * 100..(t + 1) * 100).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn sharded_pull_from_scans_lanes() {
+    let queue = ShardedConcurrentQueue::new(4);
+    queue.extend_to(2, 0..10);
+
+    let mut collected = Vec::new();
+    while let Some(chunk) = queue.pull_from(0, 3) {
+        assert!(chunk.len() <= 3);
+        collected.extend(chunk);
+    }
+
+    assert_eq!(collected, (0..10).collect::<Vec<_>>());
+    assert_eq!(queue.len(), 0);
+}
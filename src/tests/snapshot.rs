@@ -0,0 +1,187 @@
+use crate::queue::ConcurrentQueue;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_POPPERS: usize = 3;
+const NUM_SNAPSHOTS: usize = 16;
+
+fn assert_consecutive(snapshot: &[String]) {
+    let values: Vec<usize> = snapshot
+        .iter()
+        .map(|x| x.parse().expect("pushed elements are numbers"))
+        .collect();
+    if let Some(first) = values.first() {
+        let expected: Vec<_> = (*first..(*first + values.len())).collect();
+        assert_eq!(values, expected);
+    }
+}
+
+#[test_matrix([
+    FixedVec::new(N),
+    SplitVec::with_doubling_growth_and_max_concurrent_capacity(),
+    SplitVec::with_linear_growth_and_fragments_capacity(6, N / 64 + 1)
+])]
+fn snapshot_while_pushing_and_popping<P>(vec: P)
+where
+    P: IntoConcurrentPinnedVec<String>,
+{
+    let queue: ConcurrentQueue<String, _> = vec.into();
+    let q = &queue;
+
+    let popped: Vec<String> = std::thread::scope(|s| {
+        // a single pusher; hence, positions and values match
+        s.spawn(move || {
+            for i in 0..N {
+                q.push(i.to_string());
+            }
+        });
+
+        let poppers: Vec<_> = (0..NUM_POPPERS)
+            .map(|_| {
+                s.spawn(move || {
+                    let mut popped = Vec::new();
+                    while popped.len() < N / NUM_POPPERS {
+                        popped.extend(q.pop());
+                    }
+                    popped
+                })
+            })
+            .collect();
+
+        for _ in 0..NUM_SNAPSHOTS {
+            assert_consecutive(&q.snapshot());
+
+            let mut visited = Vec::new();
+            q.for_each_pending(|x| visited.push(x.clone()));
+            assert_consecutive(&visited);
+        }
+
+        poppers
+            .into_iter()
+            .flat_map(|x| x.join().expect("poppers do not panic"))
+            .collect()
+    });
+
+    let mut all: Vec<usize> = popped
+        .into_iter()
+        .chain(queue.snapshot())
+        .map(|x| x.parse().expect("pushed elements are numbers"))
+        .collect();
+    all.sort();
+    assert_eq!(all, (0..N).collect::<Vec<_>>());
+}
+
+#[test]
+fn pops_progress_under_back_to_back_snapshots() {
+    let queue: ConcurrentQueue<String, _> =
+        SplitVec::with_doubling_growth_and_max_concurrent_capacity().into();
+    queue.extend((0..N).map(|x| x.to_string()));
+    let q = &queue;
+    let num_popped = AtomicUsize::new(0);
+    let num_popped = &num_popped;
+
+    std::thread::scope(|s| {
+        for _ in 0..2 {
+            s.spawn(move || {
+                // snapshots overlap each other, there is always at least one active reader
+                while num_popped.load(Ordering::Relaxed) < N {
+                    assert_consecutive(&q.snapshot());
+                }
+            });
+        }
+
+        for _ in 0..NUM_POPPERS {
+            s.spawn(move || {
+                while q.pop().is_some() {
+                    _ = num_popped.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn readers_beyond_the_number_of_reader_slots() {
+    fn nested_sum(queue: &ConcurrentQueue<String>, depth: usize) -> usize {
+        let mut sum = 0;
+        queue.for_each_pending(|x| {
+            sum += x.len();
+            if depth > 0 {
+                sum += nested_sum(queue, depth - 1);
+            }
+        });
+        sum
+    }
+
+    let queue = ConcurrentQueue::new();
+    queue.push("a".to_string());
+    assert_eq!(nested_sum(&queue, 12), 13);
+
+    let a: ConcurrentQueue<String> = (0..N).map(|x| x.to_string()).collect();
+    let b = a.clone();
+    let c = a.clone();
+    let (a, b, c) = (&a, &b, &c);
+    std::thread::scope(|s| {
+        for t in 0..16 {
+            s.spawn(move || {
+                for _ in 0..8 {
+                    match t % 4 {
+                        0 => assert!(a == b),
+                        1 => assert!(b == a),
+                        2 => assert!(a == a),
+                        _ => assert_consecutive(&c.snapshot()),
+                    }
+                }
+            });
+        }
+        for _ in 0..NUM_POPPERS {
+            s.spawn(move || while c.pop().is_some() {});
+        }
+    });
+    assert!(c.is_empty());
+}
+
+#[test]
+fn push_within_for_each_pending() {
+    let queue = ConcurrentQueue::new();
+    queue.extend((0..3).map(|x| x.to_string()));
+
+    let mut visited = Vec::new();
+    queue.for_each_pending(|x| {
+        visited.push(x.clone());
+        queue.push(x.repeat(2));
+    });
+    assert_eq!(visited, ["0", "1", "2"]);
+
+    let popped: Vec<_> = core::iter::from_fn(|| queue.pop()).collect();
+    assert_eq!(popped, ["0", "1", "2", "00", "11", "22"]);
+}
+
+#[test]
+fn snapshot_does_not_consume() {
+    let queue = ConcurrentQueue::new();
+    assert!(queue.snapshot().is_empty());
+
+    queue.extend((0..5).map(|x| x.to_string()));
+    _ = queue.pop();
+    let expected: Vec<_> = (1..5).map(|x| x.to_string()).collect();
+    assert_eq!(queue.snapshot(), expected);
+    assert_eq!(queue.snapshot(), expected);
+
+    let mut count = 0;
+    queue.for_each_pending(|_| count += 1);
+    assert_eq!(count, 4);
+    assert_eq!(queue.len(), 4);
+}
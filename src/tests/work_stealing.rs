@@ -0,0 +1,150 @@
+use crate::WorkStealingQueue;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use orx_concurrent_bag::ConcurrentBag;
+use orx_fixed_vec::FixedVec;
+use orx_pinned_vec::IntoConcurrentPinnedVec;
+use orx_split_vec::SplitVec;
+use std::fmt::Debug;
+use test_case::test_matrix;
+
+#[cfg(miri)]
+const N: usize = 51;
+#[cfg(not(miri))]
+const N: usize = 4735;
+
+const NUM_THIEVES: usize = 4;
+
+#[test_matrix(
+    [FixedVec::new(N * 4), SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 64)],
+    [|x| x, |x| x.to_string()],
+    [1, 3, 64]
+)]
+fn owner_pop_and_steal<P, T>(vec: P, f: impl Fn(usize) -> T + Sync, batch_size: usize)
+where
+    P: IntoConcurrentPinnedVec<T>,
+    T: Send + Clone + Ord + Debug,
+{
+    assert!(vec.is_empty());
+
+    let queue = WorkStealingQueue::from(vec);
+    let q = &queue;
+    let f = &f;
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            let mut owner = q.owner().expect("single owner");
+            for i in 0..N {
+                owner.push(f(2 * i));
+                owner.push(f(2 * i + 1));
+                if let Some(x) = owner.pop() {
+                    collected.push(x);
+                }
+            }
+            while let Some(x) = owner.pop() {
+                collected.push(x);
+            }
+        });
+
+        for t in 0..NUM_THIEVES {
+            let collected = &collected;
+            s.spawn(move || {
+                for _ in 0..N {
+                    match t % 2 {
+                        0 => {
+                            if let Some(x) = q.steal() {
+                                collected.push(x);
+                            }
+                        }
+                        _ => {
+                            if let Some(chunk) = q.steal_batch(batch_size) {
+                                assert!(chunk.len() > 0 && chunk.len() <= batch_size);
+                                for x in chunk {
+                                    collected.push(x);
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    assert!(queue.is_empty());
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.sort();
+
+    let mut expected: Vec<_> = (0..2 * N).map(f).collect();
+    expected.sort();
+
+    assert_eq!(collected, expected);
+}
+
+#[test_matrix(
+    [SplitVec::with_doubling_growth_and_max_concurrent_capacity(), SplitVec::with_linear_growth_and_fragments_capacity(10, 1024)],
+    [|x| x, |x| x.to_string()]
+)]
+fn steal_half_among_workers<P, T>(vec: P, f: impl Fn(usize) -> T + Sync)
+where
+    P: IntoConcurrentPinnedVec<T> + Clone,
+    T: Send + Clone + Ord + Debug,
+{
+    let queues: Vec<_> = (0..NUM_THIEVES)
+        .map(|_| WorkStealingQueue::from(vec.clone()))
+        .collect();
+    queues[0]
+        .owner()
+        .expect("single owner")
+        .extend((0..N).map(&f));
+
+    let collected = ConcurrentBag::new();
+
+    std::thread::scope(|s| {
+        for t in 0..NUM_THIEVES {
+            let queues = &queues;
+            let collected = &collected;
+            s.spawn(move || {
+                let mut owner = queues[t].owner().expect("single owner");
+                loop {
+                    match owner.pop() {
+                        Some(x) => _ = collected.push(x),
+                        None => {
+                            let mut victims =
+                                (1..NUM_THIEVES).map(|i| &queues[(t + i) % NUM_THIEVES]);
+                            match victims.find_map(|q| q.steal_half()) {
+                                Some(stolen) => owner.extend(stolen),
+                                None => break,
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    let mut collected = collected.into_inner().to_vec();
+    collected.sort();
+
+    let mut expected: Vec<_> = (0..N).map(f).collect();
+    expected.sort();
+
+    assert_eq!(collected, expected);
+}
+
+#[test]
+fn drop_with_remaining_elements() {
+    let queue = WorkStealingQueue::new();
+    queue
+        .owner()
+        .expect("single owner")
+        .extend((0..100).map(|x| x.to_string()));
+
+    let stolen = queue.steal_batch(10).expect("non-empty");
+    assert_eq!(stolen.len(), 10);
+    drop(stolen);
+
+    assert_eq!(queue.steal(), Some(10.to_string()));
+    assert_eq!(queue.len(), 89);
+}
@@ -0,0 +1,66 @@
+use core::time::Duration;
+
+/// Strategy determining how a thread waits while the queue does not yet have enough elements,
+/// such as while [`pull_batch`] waits to fill a batch.
+///
+/// [`pull_batch`]: crate::ConcurrentQueue::pull_batch
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::WaitStrategy;
+/// use std::time::Duration;
+///
+/// assert_eq!(
+///     WaitStrategy::default(),
+///     WaitStrategy::YieldThenSleep {
+///         num_yields: 64,
+///         max_sleep: Duration::from_micros(100),
+///     }
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitStrategy {
+    /// The thread busy-waits with a spin loop hint, which has the lowest latency but keeps the core busy.
+    Spin,
+    /// The thread yields its time slice to the other threads on every wait.
+    Yield,
+    /// The thread yields its time slice for the first `num_yields` waits, and sleeps for `max_sleep`
+    /// afterwards, or until the deadline of the wait if it is sooner.
+    ///
+    /// This is the default strategy with 64 yields and sleeps of 100 microseconds.
+    YieldThenSleep {
+        /// Number of consecutive waits in which the thread yields before it starts to sleep.
+        num_yields: usize,
+        /// Maximum duration of a single sleep.
+        max_sleep: Duration,
+    },
+}
+
+impl Default for WaitStrategy {
+    fn default() -> Self {
+        Self::YieldThenSleep {
+            num_yields: 64,
+            max_sleep: Duration::from_micros(100),
+        }
+    }
+}
+
+impl WaitStrategy {
+    /// Waits once, where `num_waits` is the number of prior consecutive waits and `remaining` is the
+    /// duration until the deadline of the wait.
+    #[cfg(feature = "std")]
+    pub(crate) fn wait(&self, num_waits: usize, remaining: Duration) {
+        match *self {
+            Self::Spin => core::hint::spin_loop(),
+            Self::Yield => std::thread::yield_now(),
+            Self::YieldThenSleep {
+                num_yields,
+                max_sleep,
+            } => match num_waits < num_yields {
+                true => std::thread::yield_now(),
+                false => std::thread::sleep(max_sleep.min(remaining)),
+            },
+        }
+    }
+}
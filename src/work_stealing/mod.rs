@@ -0,0 +1,5 @@
+mod owner;
+mod queue;
+
+pub use owner::WorkStealingOwner;
+pub use queue::WorkStealingQueue;
@@ -0,0 +1,124 @@
+use crate::WorkStealingQueue;
+use core::{cell::Cell, marker::PhantomData};
+use orx_pinned_vec::ConcurrentPinnedVec;
+
+/// Owner handle of a [`WorkStealingQueue`] which is allowed to push to and pop from the back of the deque.
+///
+/// The handle can be sent to another thread; however, it cannot be shared among threads.
+/// This guarantees that the back of the deque is accessed by a single thread at a time.
+///
+/// The handle is created by [`WorkStealingQueue::owner`] and ownership of the deque is released when the handle is dropped.
+pub struct WorkStealingOwner<'a, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    queue: &'a WorkStealingQueue<T, P>,
+    not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T, P> Drop for WorkStealingOwner<'_, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn drop(&mut self) {
+        self.queue.release_owner();
+    }
+}
+
+impl<'a, T, P> WorkStealingOwner<'a, T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    pub(super) fn new(queue: &'a WorkStealingQueue<T, P>) -> Self {
+        Self {
+            queue,
+            not_sync: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the work stealing deque that this handle owns.
+    pub fn queue(&self) -> &'a WorkStealingQueue<T, P> {
+        self.queue
+    }
+
+    /// Pushes the `value` to the back of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying pinned vector cannot grow any further.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// let mut owner = queue.owner().unwrap();
+    ///
+    /// owner.push(1);
+    /// owner.push(2);
+    /// assert_eq!(queue.len(), 2);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        self.queue.owner_push(value);
+    }
+
+    /// Extends the deque by pushing `values` elements to the back of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying pinned vector cannot grow any further.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// let mut owner = queue.owner().unwrap();
+    ///
+    /// owner.extend(1..3);
+    /// owner.extend(vec![3, 4]);
+    /// assert_eq!(queue.len(), 4);
+    /// ```
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for x in values {
+            self.queue.owner_push(x);
+        }
+    }
+
+    /// Pops and returns the element in the back of the deque; returns None if the deque is empty.
+    ///
+    /// Note that the owner pops the most recently pushed element, while thieves steal the oldest elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// let mut owner = queue.owner().unwrap();
+    ///
+    /// owner.extend(1..4);
+    /// assert_eq!(owner.pop(), Some(3));
+    /// assert_eq!(owner.pop(), Some(2));
+    /// assert_eq!(owner.pop(), Some(1));
+    /// assert_eq!(owner.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.queue.owner_pop()
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if the deque is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
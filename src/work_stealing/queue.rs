@@ -0,0 +1,461 @@
+use crate::{
+    DefaultConPinnedVec, common_traits::iter::QueueIterOwned, work_stealing::WorkStealingOwner,
+};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use orx_pinned_vec::{ConcurrentPinnedVec, IntoConcurrentPinnedVec};
+use orx_split_vec::SplitVec;
+
+/// A work stealing deque which is meant to be used as the local queue of a single worker thread.
+///
+/// * The owner of the deque, which is accessed through the [`owner`] handle, pushes to and pops from the back of the deque.
+///   Owner pops in LIFO order which favors cache locality of the recently created tasks.
+/// * Any other thread, the thieves, can steal elements from the front of the deque with [`steal`], [`steal_batch`] or [`steal_half`]
+///   using a shared reference.
+///
+/// This allows to replace a single global queue, where all threads compete on the same state, with per-worker queues
+/// that are mostly accessed by their owners and only touched by other threads when they run out of work.
+///
+/// Similar to [`ConcurrentQueue`], the deque is backed by a concurrent pinned vector; therefore, elements are never moved
+/// while the deque grows.
+///
+/// [`owner`]: crate::WorkStealingQueue::owner
+/// [`steal`]: crate::WorkStealingQueue::steal
+/// [`steal_batch`]: crate::WorkStealingQueue::steal_batch
+/// [`steal_half`]: crate::WorkStealingQueue::steal_half
+/// [`ConcurrentQueue`]: crate::ConcurrentQueue
+///
+/// # Examples
+///
+/// ```
+/// use orx_concurrent_queue::WorkStealingQueue;
+///
+/// let queue = WorkStealingQueue::new();
+///
+/// let mut owner = queue.owner().unwrap();
+/// owner.extend(0..8); // [0, 1, 2, 3, 4, 5, 6, 7]
+///
+/// // owner pops from the back
+/// assert_eq!(owner.pop(), Some(7)); // [0, 1, 2, 3, 4, 5, 6]
+///
+/// // thieves steal from the front
+/// assert_eq!(queue.steal(), Some(0)); // [1, 2, 3, 4, 5, 6]
+///
+/// let stolen: Vec<_> = queue.steal_half().unwrap().collect(); // [4, 5, 6]
+/// assert_eq!(stolen, vec![1, 2, 3]);
+///
+/// let stolen: Vec<_> = queue.steal_batch(2).unwrap().collect(); // [6]
+/// assert_eq!(stolen, vec![4, 5]);
+///
+/// assert_eq!(owner.pop(), Some(6));
+/// assert_eq!(owner.pop(), None);
+/// assert_eq!(queue.steal(), None);
+/// ```
+///
+/// The following example demonstrates a parallel computation where each worker owns a deque
+/// and steals half of the work of the others once its own deque is exhausted.
+///
+/// ```
+/// use orx_concurrent_queue::WorkStealingQueue;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let num_threads = 4;
+/// let queues: Vec<_> = (0..num_threads).map(|_| WorkStealingQueue::new()).collect();
+///
+/// // all work is initially created in the first deque
+/// queues[0].owner().unwrap().extend(0..1000);
+///
+/// let sum = AtomicUsize::new(0);
+///
+/// std::thread::scope(|s| {
+///     for t in 0..num_threads {
+///         let queues = &queues;
+///         let sum = &sum;
+///         s.spawn(move || {
+///             let mut owner = queues[t].owner().unwrap();
+///             loop {
+///                 match owner.pop() {
+///                     Some(x) => _ = sum.fetch_add(x, Ordering::Relaxed),
+///                     None => {
+///                         let victims = (1..num_threads).map(|i| &queues[(t + i) % num_threads]);
+///                         match victims.filter_map(|q| q.steal_half()).next() {
+///                             Some(stolen) => owner.extend(stolen),
+///                             None => break,
+///                         }
+///                     }
+///                 }
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(sum.into_inner(), 999 * 1000 / 2);
+/// ```
+pub struct WorkStealingQueue<T, P = DefaultConPinnedVec<T>>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    vec: P,
+    phantom: PhantomData<T>,
+    top: AtomicUsize,
+    bottom: AtomicUsize,
+    has_owner: AtomicBool,
+}
+
+unsafe impl<T, P> Sync for WorkStealingQueue<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+}
+
+impl<T, P> Drop for WorkStealingQueue<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<T>() {
+            let top = self.top.load(Ordering::Relaxed);
+            let bottom = self.bottom.load(Ordering::Relaxed);
+            for i in top..bottom {
+                let ptr = unsafe { self.ptr(i) };
+                unsafe { ptr.drop_in_place() };
+            }
+        }
+        unsafe { self.vec.set_pinned_vec_len(0) };
+    }
+}
+
+impl<T, P> From<P> for WorkStealingQueue<T, P::ConPinnedVec>
+where
+    T: Send,
+    P: IntoConcurrentPinnedVec<T>,
+{
+    fn from(vec: P) -> Self {
+        Self {
+            phantom: PhantomData,
+            top: 0.into(),
+            bottom: vec.len().into(),
+            has_owner: false.into(),
+            vec: vec.into_concurrent(),
+        }
+    }
+}
+
+impl<T> Default for WorkStealingQueue<T, DefaultConPinnedVec<T>>
+where
+    T: Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> WorkStealingQueue<T, DefaultConPinnedVec<T>>
+where
+    T: Send,
+{
+    /// Creates a new empty work stealing deque.
+    ///
+    /// This deque is backed with default concurrent pinned vec, which is the concurrent version of [`SplitVec`] with [`Doubling`] growth.
+    ///
+    /// In order to create a deque backed with a particular [`PinnedVec`], you may use the `From` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    /// use orx_split_vec::SplitVec;
+    /// use orx_fixed_vec::FixedVec;
+    ///
+    /// let queue: WorkStealingQueue<usize> = WorkStealingQueue::new();
+    ///
+    /// // in order to create a deque from a different pinned vec, use into, rather than new:
+    /// let queue: WorkStealingQueue<usize, _> = SplitVec::with_linear_growth_and_fragments_capacity(10, 64).into();
+    /// let queue: WorkStealingQueue<usize, _> = FixedVec::new(1000).into();
+    /// ```
+    ///
+    /// [`SplitVec`]: orx_split_vec::SplitVec
+    /// [`Doubling`]: orx_split_vec::Doubling
+    /// [`PinnedVec`]: orx_pinned_vec::PinnedVec
+    pub fn new() -> Self {
+        SplitVec::with_doubling_growth_and_max_concurrent_capacity().into()
+    }
+}
+
+impl<T, P> WorkStealingQueue<T, P>
+where
+    T: Send,
+    P: ConcurrentPinnedVec<T>,
+{
+    /// Returns the owner handle of the deque which is allowed to push to and pop from the back of the deque;
+    /// returns None if another owner handle is currently alive.
+    ///
+    /// There can be at most one owner at a time.
+    /// Once the returned handle is dropped, a new owner can be obtained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    ///
+    /// let mut owner = queue.owner().unwrap();
+    /// owner.push(42);
+    /// assert!(queue.owner().is_none());
+    ///
+    /// drop(owner);
+    ///
+    /// let mut owner = queue.owner().unwrap();
+    /// assert_eq!(owner.pop(), Some(42));
+    /// ```
+    pub fn owner(&self) -> Option<WorkStealingOwner<'_, T, P>> {
+        self.has_owner
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+            .then(|| WorkStealingOwner::new(self))
+    }
+
+    // steal
+
+    /// Steals and returns the element in the front of the deque; returns None if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// queue.owner().unwrap().extend(1..4);
+    ///
+    /// assert_eq!(queue.steal(), Some(1));
+    /// assert_eq!(queue.steal(), Some(2));
+    /// assert_eq!(queue.steal(), Some(3));
+    /// assert_eq!(queue.steal(), None);
+    /// ```
+    pub fn steal(&self) -> Option<T> {
+        self.claim_front()
+            .map(|idx| unsafe { self.ptr(idx).read() })
+    }
+
+    /// Steals at most `chunk_size` elements from the front of the deque:
+    ///
+    /// * returns None if `chunk_size` is zero,
+    /// * returns Some of an ExactSizeIterator with `len = chunk_size` if the deque has at least `chunk_size` items,
+    /// * returns Some of a non-empty ExactSizeIterator with `0 < len < chunk_size` if the deque has fewer elements
+    ///   or if the owner or other thieves concurrently take elements,
+    /// * returns None if the deque is empty.
+    ///
+    /// Therefore, if the method returns a Some variant, the exact size iterator is not empty.
+    ///
+    /// Stolen elements are guaranteed to be consecutive elements in the deque.
+    ///
+    /// Since the owner can concurrently pop elements from the back, elements are claimed one at a time;
+    /// however, stealing stops as soon as a conflict with another thread is detected.
+    /// Claimed elements are then returned as one chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// queue.owner().unwrap().extend(1..6);
+    ///
+    /// assert_eq!(
+    ///     queue.steal_batch(2).map(|x| x.collect::<Vec<_>>()),
+    ///     Some(vec![1, 2])
+    /// );
+    /// assert_eq!(
+    ///     queue.steal_batch(7).map(|x| x.collect::<Vec<_>>()),
+    ///     Some(vec![3, 4, 5])
+    /// );
+    /// assert_eq!(queue.steal_batch(1).map(|x| x.collect::<Vec<_>>()), None);
+    /// ```
+    pub fn steal_batch(&self, chunk_size: usize) -> Option<QueueIterOwned<'_, T, P>> {
+        match chunk_size > 0 {
+            true => self.claim_front().map(|begin_idx| {
+                let mut end_idx = begin_idx + 1;
+                while end_idx - begin_idx < chunk_size {
+                    let bottom = self.bottom.load(Ordering::SeqCst);
+                    let claimed = end_idx < bottom
+                        && self
+                            .top
+                            .compare_exchange(
+                                end_idx,
+                                end_idx + 1,
+                                Ordering::SeqCst,
+                                Ordering::Relaxed,
+                            )
+                            .is_ok();
+                    match claimed {
+                        true => end_idx += 1,
+                        false => break,
+                    }
+                }
+
+                let iter = unsafe { self.vec.ptr_iter_unchecked(begin_idx..end_idx) };
+                QueueIterOwned::new(iter)
+            }),
+            false => None,
+        }
+    }
+
+    /// Steals half of the elements, rounded up, from the front of the deque; returns None if the deque is empty.
+    ///
+    /// This is a shorthand for `steal_batch(n)` where `n` is half of the current length of the deque.
+    /// It is the common choice of the idle workers since it balances the remaining work between the
+    /// victim and the thief.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// queue.owner().unwrap().extend(1..6);
+    ///
+    /// assert_eq!(
+    ///     queue.steal_half().map(|x| x.collect::<Vec<_>>()),
+    ///     Some(vec![1, 2, 3])
+    /// );
+    /// assert_eq!(
+    ///     queue.steal_half().map(|x| x.collect::<Vec<_>>()),
+    ///     Some(vec![4])
+    /// );
+    /// assert_eq!(
+    ///     queue.steal_half().map(|x| x.collect::<Vec<_>>()),
+    ///     Some(vec![5])
+    /// );
+    /// assert_eq!(queue.steal_half().map(|x| x.collect::<Vec<_>>()), None);
+    /// ```
+    pub fn steal_half(&self) -> Option<QueueIterOwned<'_, T, P>> {
+        self.steal_batch(self.len().div_ceil(2).max(1))
+    }
+
+    // get
+
+    /// Returns the number of elements in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// queue.owner().unwrap().extend(1..5);
+    /// assert_eq!(queue.len(), 4);
+    ///
+    /// _ = queue.steal();
+    /// assert_eq!(queue.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.bottom
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.top.load(Ordering::Relaxed))
+    }
+
+    /// Returns true if the deque is empty, false otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orx_concurrent_queue::WorkStealingQueue;
+    ///
+    /// let queue = WorkStealingQueue::new();
+    /// assert!(queue.is_empty());
+    ///
+    /// queue.owner().unwrap().push(1);
+    /// assert!(!queue.is_empty());
+    ///
+    /// _ = queue.steal();
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // owner
+
+    pub(super) fn owner_push(&self, value: T) {
+        let idx = self.bottom.load(Ordering::Relaxed);
+        assert!(
+            idx < self.vec.max_capacity(),
+            "Out of capacity. Underlying pinned vector cannot grow any further while being concurrently safe."
+        );
+
+        if idx >= self.vec.capacity() {
+            _ = self
+                .vec
+                .grow_to(idx + 1)
+                .expect("The underlying pinned vector reached its capacity and failed to grow");
+        }
+
+        unsafe { self.ptr(idx).write(value) };
+        self.bottom.store(idx + 1, Ordering::Release);
+    }
+
+    pub(super) fn owner_pop(&self) -> Option<T> {
+        let bottom = self.bottom.load(Ordering::Relaxed);
+        if bottom == 0 {
+            return None;
+        }
+
+        let idx = bottom - 1;
+        self.bottom.store(idx, Ordering::SeqCst);
+        let top = self.top.load(Ordering::SeqCst);
+
+        match top.cmp(&idx) {
+            core::cmp::Ordering::Less => Some(unsafe { self.ptr(idx).read() }),
+            core::cmp::Ordering::Equal => {
+                // last element: compete with the thieves
+                let won = self
+                    .top
+                    .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+                    .is_ok();
+                self.bottom.store(bottom, Ordering::SeqCst);
+                won.then(|| unsafe { self.ptr(idx).read() })
+            }
+            core::cmp::Ordering::Greater => {
+                self.bottom.store(bottom, Ordering::SeqCst);
+                None
+            }
+        }
+    }
+
+    pub(super) fn release_owner(&self) {
+        self.has_owner.store(false, Ordering::Release);
+    }
+
+    // helpers
+
+    #[inline(always)]
+    unsafe fn ptr(&self, idx: usize) -> *mut T {
+        unsafe { self.vec.get_ptr_mut(idx) }
+    }
+
+    /// Claims the element at the front of the deque and returns its position;
+    /// returns None if the deque is empty.
+    fn claim_front(&self) -> Option<usize> {
+        loop {
+            let top = self.top.load(Ordering::SeqCst);
+            let bottom = self.bottom.load(Ordering::SeqCst);
+            if top >= bottom {
+                return None;
+            }
+
+            if self
+                .top
+                .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Some(top);
+            }
+        }
+    }
+}